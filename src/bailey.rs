@@ -0,0 +1,104 @@
+// The implementation is the six-step variant of Bailey’s algorithm, which
+// expresses a transform of n = n1 * n2 points in terms of transforms of n1 and
+// n2 points of contiguous data interleaved with transpositions:
+// https://en.wikipedia.org/wiki/Bailey%27s_FFT_algorithm
+
+use num_complex::Complex;
+use num_traits::{Float, FloatConst};
+
+use complex::compute_with;
+use {table, twiddle, Operation, Options, Plan, Result};
+
+const BLOCK: usize = 32;
+
+/// A transform for large composite lengths.
+#[derive(Clone, Debug)]
+pub struct Bailey<T> {
+    rows: Plan<T>,
+    columns: Plan<T>,
+    factors: Vec<Complex<T>>,
+}
+
+impl<T> Bailey<T>
+where
+    T: Float + FloatConst + 'static,
+{
+    pub fn new(operation: Operation, n1: usize, n2: usize, options: Options) -> Result<Self> {
+        let n = n1 * n2;
+        let options = Options {
+            four_step: None,
+            ..options
+        };
+        let mut factors = table(n)?;
+        for j1 in 0..n1 {
+            factors.extend((0..n2).map(|k2| twiddle((j1 * k2) % n, n, operation)));
+        }
+        Ok(Bailey {
+            rows: Plan::complex(operation, n1, options)?,
+            columns: Plan::complex(operation, n2, options)?,
+            factors,
+        })
+    }
+}
+
+impl<T> Bailey<T> {
+    pub fn scratch(&self) -> usize {
+        self.rows.n * self.columns.n + self.rows.scratch().max(self.columns.scratch())
+    }
+}
+
+impl<T> Bailey<T>
+where
+    T: Float + 'static,
+{
+    pub fn transform(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        let (n1, n2) = (self.rows.n, self.columns.n);
+        let (buffer, scratch) = scratch.split_at_mut(n1 * n2);
+        transpose(data, buffer, n2, n1);
+        for (row, factors) in buffer.chunks_mut(n2).zip(self.factors.chunks(n2)) {
+            compute_with(row, scratch, &self.columns);
+            for (value, &factor) in row.iter_mut().zip(factors) {
+                *value = *value * factor;
+            }
+        }
+        transpose(buffer, data, n1, n2);
+        for row in data.chunks_mut(n1) {
+            compute_with(row, scratch, &self.rows);
+        }
+        transpose(data, buffer, n2, n1);
+        data.copy_from_slice(buffer);
+    }
+}
+
+/// Split a number into two factors as close to each other as possible.
+///
+/// The function returns `None` if the number is prime.
+pub fn split(n: usize) -> Option<(usize, usize)> {
+    let mut n1 = (n as f64).sqrt() as usize;
+    while n1 > 1 && !n.is_multiple_of(n1) {
+        n1 -= 1;
+    }
+    if n1 > 1 {
+        Some((n / n1, n1))
+    } else {
+        None
+    }
+}
+
+fn transpose<T>(source: &[Complex<T>], target: &mut [Complex<T>], rows: usize, columns: usize)
+where
+    T: Copy,
+{
+    for i in (0..rows).step_by(BLOCK) {
+        for j in (0..columns).step_by(BLOCK) {
+            for k in i..rows.min(i + BLOCK) {
+                for l in j..columns.min(j + BLOCK) {
+                    unsafe {
+                        *target.get_unchecked_mut(l * rows + k) =
+                            *source.get_unchecked(k * columns + l);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+// The implementation expresses the transform as a convolution, which is
+// computed using a radix-2 transform of a padded length:
+// https://en.wikipedia.org/wiki/Chirp_Z-transform#Bluestein.27s_algorithm
+
+use num_complex::Complex;
+use num_traits::{Float, FloatConst};
+
+use complex::{compute, compute_with};
+use {table, twiddle, Operation, Options, Plan, Result};
+
+/// A transform for lengths with large prime factors.
+#[derive(Clone, Debug)]
+pub struct Bluestein<T> {
+    chirp: Vec<Complex<T>>,
+    spectrum: Vec<Complex<T>>,
+    plan: Plan<T>,
+}
+
+impl<T> Bluestein<T>
+where
+    T: Float + FloatConst + 'static,
+{
+    pub fn new(operation: Operation, n: usize, options: Options) -> Result<Self> {
+        let m = (2 * n - 1).next_power_of_two();
+        let mut chirp = table(n)?;
+        chirp.extend(
+            (0..n)
+                .map(|k| (k as u128 * k as u128 % (2 * n) as u128) as usize)
+                .map(|k| twiddle(k, 2 * n, operation)),
+        );
+        let plan = Plan::complex(Operation::Forward, m, options)?;
+        let factor = T::from(m).unwrap().recip();
+        let mut spectrum = table(m)?;
+        spectrum.resize(m, Complex::new(T::zero(), T::zero()));
+        spectrum[0] = chirp[0].conj().scale(factor);
+        for k in 1..n {
+            spectrum[k] = chirp[k].conj().scale(factor);
+            spectrum[m - k] = spectrum[k];
+        }
+        compute(&mut spectrum, &plan);
+        Ok(Bluestein {
+            chirp,
+            spectrum,
+            plan,
+        })
+    }
+}
+
+impl<T> Bluestein<T> {
+    pub fn scratch(&self) -> usize {
+        self.plan.n + self.plan.scratch()
+    }
+}
+
+impl<T> Bluestein<T>
+where
+    T: Float + 'static,
+{
+    pub fn transform(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        let (buffer, scratch) = scratch.split_at_mut(self.plan.n);
+        let (head, tail) = buffer.split_at_mut(data.len());
+        for ((value, &datum), &chirp) in head.iter_mut().zip(data.iter()).zip(&self.chirp) {
+            *value = datum * chirp;
+        }
+        for value in tail {
+            *value = Complex::new(T::zero(), T::zero());
+        }
+        compute_with(buffer, scratch, &self.plan);
+        for (value, &spectrum) in buffer.iter_mut().zip(&self.spectrum) {
+            *value = (*value * spectrum).conj();
+        }
+        compute_with(buffer, scratch, &self.plan);
+        for ((datum, &value), &chirp) in data.iter_mut().zip(buffer.iter()).zip(&self.chirp) {
+            *datum = value.conj() * chirp;
+        }
+    }
+}
@@ -1,63 +1,423 @@
 // The implementation is based on:
 // http://www.librow.com/articles/article-10
+//
+// The stages are fused into radix-4 butterflies, and large transforms are
+// decomposed using the split-radix algorithm:
+// https://en.wikipedia.org/wiki/Split-radix_FFT_algorithm
+//
+// The out-of-place kernel is a radix-4 decimation-in-frequency Stockham
+// algorithm: http://wwwa.pikara.ne.jp/okojisan/otfft-en/stockham1.html
 
 use num_complex::Complex;
 use num_traits::Float;
 
-use {Operation, Plan, Transform};
+use {Algorithm, Error, Kernel, Operation, Plan, Result, Simd, Transform};
+
+pub const SPLIT: usize = 512;
 
 impl<T> Transform<T> for [Complex<T>]
 where
-    T: Float,
+    T: Float + 'static,
 {
-    fn transform(&mut self, plan: &Plan<T>) {
+    fn try_transform(&mut self, plan: &Plan<T>) -> Result<()> {
+        self.try_transform_with_buffer(plan, &mut Vec::new())
+    }
+
+    fn try_transform_with_buffer(
+        &mut self,
+        plan: &Plan<T>,
+        buffer: &mut Vec<Complex<T>>,
+    ) -> Result<()> {
         let n = self.len();
-        assert!(n <= plan.n);
-        rearrange(self, n);
-        calculate(self, n, &plan.factors);
-        if let Operation::Inverse = plan.operation {
-            scale(self, n);
+        check(n, plan)?;
+        compute_with(self, reserve(buffer, plan.scratch()), plan);
+        if let Some(factor) = plan.factor(n) {
+            scale(self, factor);
         }
+        Ok(())
     }
 }
 
 impl<T> Transform<T> for Vec<Complex<T>>
 where
-    T: Float,
+    T: Float + 'static,
 {
     #[inline(always)]
-    fn transform(&mut self, plan: &Plan<T>) {
-        Transform::transform(&mut self[..], plan)
+    fn try_transform(&mut self, plan: &Plan<T>) -> Result<()> {
+        Transform::try_transform(&mut self[..], plan)
+    }
+}
+
+/// Perform the transform using a scratch buffer.
+///
+/// The scratch buffer is used only by the Stockham kernel; see `Kernel`. The
+/// function panics if the data are not compatible with the plan or if the
+/// scratch buffer is shorter than the data.
+pub fn transform_with_scratch<T>(
+    data: &mut [Complex<T>],
+    scratch: &mut [Complex<T>],
+    plan: &Plan<T>,
+) where
+    T: Float + 'static,
+{
+    if let Err(error) = try_transform_with_scratch(data, scratch, plan) {
+        panic!("{}", error);
     }
 }
 
+/// Perform the transform using a scratch buffer if the data are compatible
+/// with the plan and the scratch buffer is not shorter than the data.
+pub fn try_transform_with_scratch<T>(
+    data: &mut [Complex<T>],
+    scratch: &mut [Complex<T>],
+    plan: &Plan<T>,
+) -> Result<()>
+where
+    T: Float + 'static,
+{
+    let n = data.len();
+    check(n, plan)?;
+    if scratch.len() < n {
+        return Err(Error::ScratchTooShort {
+            data: n,
+            scratch: scratch.len(),
+        });
+    }
+    match plan.algorithm {
+        Algorithm::Radix2 if plan.options.kernel == Kernel::Stockham => {
+            stockham(data, &mut scratch[..n], &plan.factors, sign(plan.operation));
+        }
+        _ => compute(data, plan),
+    }
+    if let Some(factor) = plan.factor(n) {
+        scale(data, factor);
+    }
+    Ok(())
+}
+
+/// Perform the transform without any scaling or validation.
+pub fn compute<T>(data: &mut [Complex<T>], plan: &Plan<T>)
+where
+    T: Float + 'static,
+{
+    let mut scratch = vec![Complex::new(T::zero(), T::zero()); plan.scratch()];
+    compute_with(data, &mut scratch, plan);
+}
+
+/// Perform the transform without any scaling or validation using a scratch
+/// buffer of the length given by `Plan::scratch`.
+pub fn compute_with<T>(data: &mut [Complex<T>], scratch: &mut [Complex<T>], plan: &Plan<T>)
+where
+    T: Float + 'static,
+{
+    match plan.algorithm {
+        Algorithm::Radix2 if plan.options.kernel == Kernel::Stockham => {
+            let n = data.len();
+            stockham(data, &mut scratch[..n], &plan.factors, sign(plan.operation));
+        }
+        Algorithm::Radix2 => {
+            rearrange(data, data.len());
+            #[cfg(feature = "parallel")]
+            {
+                if ::parallel::split(data, plan) {
+                    return;
+                }
+            }
+            butterflies(data, &plan.factors, plan.operation, plan.options.simd);
+        }
+        Algorithm::Bailey(ref bailey) => bailey.transform(data, scratch),
+        Algorithm::Mixed(ref mixed) => mixed.transform(data),
+        Algorithm::Bluestein(ref bluestein) => bluestein.transform(data, scratch),
+        Algorithm::Rader(ref rader) => rader.transform(data, scratch),
+    }
+}
+
+/// Extend a buffer if it is shorter than a given length and return that many
+/// of its elements.
+pub fn reserve<T>(buffer: &mut Vec<Complex<T>>, length: usize) -> &mut [Complex<T>]
+where
+    T: Float,
+{
+    if buffer.len() < length {
+        buffer.resize(length, Complex::new(T::zero(), T::zero()));
+    }
+    &mut buffer[..length]
+}
+
+/// Perform the butterflies on bit-reversed data.
+#[cfg_attr(not(target_arch = "x86_64"), allow(unused_variables))]
+pub fn butterflies<T>(
+    data: &mut [Complex<T>],
+    factors: &[Complex<T>],
+    operation: Operation,
+    simd: Simd,
+) where
+    T: Float + 'static,
+{
+    #[cfg(target_arch = "x86_64")]
+    {
+        if ::simd::split(data, factors, operation, simd) {
+            return;
+        }
+    }
+    split(data, factors, sign(operation));
+}
+
+/// Perform a split-radix step on the quarters of bit-reversed data whose parts
+/// have been transformed.
+///
+/// The factors are those of the first and second halves of the data.
+#[cfg(feature = "parallel")]
+#[cfg_attr(not(target_arch = "x86_64"), allow(unused_variables))]
+pub fn combine<T>(
+    quarters: &mut [&mut [Complex<T>]; 4],
+    factors: &[&[Complex<T>]; 2],
+    operation: Operation,
+    simd: Simd,
+) where
+    T: Float + 'static,
+{
+    #[cfg(target_arch = "x86_64")]
+    {
+        if ::simd::combine(quarters, factors, operation, simd) {
+            return;
+        }
+    }
+    merge(quarters, factors, sign(operation));
+}
+
+/// Split data into quarters.
 #[inline(always)]
-fn calculate<T>(data: &mut [Complex<T>], n: usize, factors: &[Complex<T>])
+pub fn quarters<T>(data: &mut [T]) -> [&mut [T]; 4] {
+    let q = data.len() >> 2;
+    let (first, rest) = data.split_at_mut(q);
+    let (second, rest) = rest.split_at_mut(q);
+    let (third, fourth) = rest.split_at_mut(q);
+    [first, second, third, fourth]
+}
+
+fn check<T>(n: usize, plan: &Plan<T>) -> Result<()> {
+    let fits = if let Algorithm::Radix2 = plan.algorithm {
+        // The factors are stored stage by stage, and the first stages of a
+        // larger plan are exactly the stages of a smaller one.
+        n <= plan.n && n.is_power_of_two()
+    } else {
+        n == plan.n
+    };
+    if !fits {
+        return Err(Error::LengthMismatch {
+            plan: plan.n,
+            data: n,
+        });
+    }
+    Ok(())
+}
+
+/// Perform radix-4 stages preceded by a radix-2 one if the number of stages is
+/// odd.
+#[inline(always)]
+fn calculate<T>(data: &mut [Complex<T>], n: usize, factors: &[Complex<T>], sign: T)
 where
     T: Float,
 {
-    let mut k = 0;
     let mut step = 1;
+    if n.trailing_zeros() & 1 == 1 {
+        radix2(data, n);
+        step = 2;
+    }
     while step < n {
-        let jump = step << 1;
-        for mut i in 0..step {
-            while i < n {
-                let j = i + step;
-                unsafe {
-                    let product = *factors.get_unchecked(k) * *data.get_unchecked(j);
-                    *data.get_unchecked_mut(j) = *data.get_unchecked(i) - product;
-                    *data.get_unchecked_mut(i) = *data.get_unchecked(i) + product;
+        radix4(data, n, step, factors, sign);
+        step <<= 2;
+    }
+}
+
+/// Perform the first stage with radix-2 butterflies, which need no factors.
+#[inline(always)]
+pub fn radix2<T>(data: &mut [Complex<T>], n: usize)
+where
+    T: Float,
+{
+    let mut i = 0;
+    while i < n {
+        unsafe {
+            let product = *data.get_unchecked(i + 1);
+            *data.get_unchecked_mut(i + 1) = *data.get_unchecked(i) - product;
+            *data.get_unchecked_mut(i) = *data.get_unchecked(i) + product;
+        }
+        i += 2;
+    }
+}
+
+/// Perform the two stages starting from a specific step with radix-4
+/// butterflies.
+#[inline(always)]
+pub fn radix4<T>(data: &mut [Complex<T>], n: usize, step: usize, factors: &[Complex<T>], sign: T)
+where
+    T: Float,
+{
+    let jump = step << 2;
+    for k in 0..step {
+        let (factor1, factor2) = unsafe {
+            (
+                *factors.get_unchecked(step - 1 + k),
+                *factors.get_unchecked(2 * step - 1 + k),
+            )
+        };
+        let factor3 = factor1 * factor2;
+        let mut i = k;
+        while i < n {
+            unsafe {
+                let x0 = *data.get_unchecked(i);
+                let x1 = factor1 * *data.get_unchecked(i + step);
+                let x2 = factor2 * *data.get_unchecked(i + 2 * step);
+                let x3 = factor3 * *data.get_unchecked(i + 3 * step);
+                let (sum1, difference1) = (x0 + x1, x0 - x1);
+                let (sum2, difference2) = (x2 + x3, rotate(x2 - x3, sign));
+                *data.get_unchecked_mut(i) = sum1 + sum2;
+                *data.get_unchecked_mut(i + step) = difference1 + difference2;
+                *data.get_unchecked_mut(i + 2 * step) = sum1 - sum2;
+                *data.get_unchecked_mut(i + 3 * step) = difference1 - difference2;
+            }
+            i += jump;
+        }
+    }
+}
+
+/// Combine the transforms of the even elements and of the two odd quarters of
+/// bit-reversed data using a split-radix step, which falls back to `calculate`
+/// once the data fit in the cache.
+fn split<T>(data: &mut [Complex<T>], factors: &[Complex<T>], sign: T)
+where
+    T: Float,
+{
+    let n = data.len();
+    if n <= SPLIT {
+        calculate(data, n, factors, sign);
+        return;
+    }
+    let (h, q) = (n >> 1, n >> 2);
+    split(&mut data[..h], factors, sign);
+    split(&mut data[h..(h + q)], factors, sign);
+    split(&mut data[(h + q)..], factors, sign);
+    let factors = [
+        &factors[(h - 1)..(h - 1 + q)],
+        &factors[(q - 1)..(2 * q - 1)],
+    ];
+    merge(&mut quarters(data), &factors, sign);
+}
+
+#[inline(always)]
+fn merge<T>(quarters: &mut [&mut [Complex<T>]; 4], factors: &[&[Complex<T>]; 2], sign: T)
+where
+    T: Float,
+{
+    let [ref mut x0, ref mut x1, ref mut z1, ref mut z3] = *quarters;
+    let [factors1, factors2] = *factors;
+    for k in 0..x0.len() {
+        unsafe {
+            let factor1 = *factors1.get_unchecked(k);
+            let factor3 = factor1 * *factors2.get_unchecked(k);
+            let y1 = factor1 * *z1.get_unchecked(k);
+            let y3 = factor3 * *z3.get_unchecked(k);
+            let (sum, difference) = (y1 + y3, rotate(y1 - y3, sign));
+            let (y0, y2) = (*x0.get_unchecked(k), *x1.get_unchecked(k));
+            *x0.get_unchecked_mut(k) = y0 + sum;
+            *z1.get_unchecked_mut(k) = y0 - sum;
+            *x1.get_unchecked_mut(k) = y2 + difference;
+            *z3.get_unchecked_mut(k) = y2 - difference;
+        }
+    }
+}
+
+/// Perform radix-4 stages, followed by a radix-2 one if the number of stages
+/// is odd, alternating between the data and the scratch buffer.
+fn stockham<T>(data: &mut [Complex<T>], scratch: &mut [Complex<T>], factors: &[Complex<T>], sign: T)
+where
+    T: Float,
+{
+    let n = data.len();
+    let (mut m, mut stride) = (n, 1);
+    let mut swapped = false;
+    while m >= 4 {
+        let q = m >> 2;
+        {
+            let (source, target) = if swapped {
+                (&*scratch, &mut *data)
+            } else {
+                (&*data, &mut *scratch)
+            };
+            for p in 0..q {
+                let (factor1, factor2) = unsafe {
+                    (
+                        *factors.get_unchecked((m >> 1) - 1 + p),
+                        *factors.get_unchecked(q - 1 + p),
+                    )
+                };
+                let factor3 = factor1 * factor2;
+                for k in 0..stride {
+                    unsafe {
+                        let x0 = *source.get_unchecked(k + stride * p);
+                        let x1 = *source.get_unchecked(k + stride * (p + q));
+                        let x2 = *source.get_unchecked(k + stride * (p + 2 * q));
+                        let x3 = *source.get_unchecked(k + stride * (p + 3 * q));
+                        let (sum1, difference1) = (x0 + x2, x0 - x2);
+                        let (sum2, difference2) = (x1 + x3, rotate(x1 - x3, sign));
+                        let i = k + stride * 4 * p;
+                        *target.get_unchecked_mut(i) = sum1 + sum2;
+                        *target.get_unchecked_mut(i + stride) =
+                            factor1 * (difference1 + difference2);
+                        *target.get_unchecked_mut(i + 2 * stride) = factor2 * (sum1 - sum2);
+                        *target.get_unchecked_mut(i + 3 * stride) =
+                            factor3 * (difference1 - difference2);
+                    }
                 }
-                i += jump;
             }
-            k += 1;
         }
-        step <<= 1;
+        swapped = !swapped;
+        m = q;
+        stride <<= 2;
+    }
+    if m == 2 {
+        let (source, target) = if swapped {
+            (&*scratch, &mut *data)
+        } else {
+            (&*data, &mut *scratch)
+        };
+        for k in 0..stride {
+            let (x0, x1) = (source[k], source[k + stride]);
+            target[k] = x0 + x1;
+            target[k + stride] = x0 - x1;
+        }
+        swapped = !swapped;
+    }
+    if swapped {
+        data.copy_from_slice(scratch);
     }
 }
 
 #[inline(always)]
-fn rearrange<T>(data: &mut [Complex<T>], n: usize) {
+fn sign<T>(operation: Operation) -> T
+where
+    T: Float,
+{
+    let one = T::one();
+    if let Operation::Forward = operation {
+        -one
+    } else {
+        one
+    }
+}
+
+#[inline(always)]
+fn rotate<T>(value: Complex<T>, sign: T) -> Complex<T>
+where
+    T: Float,
+{
+    Complex::new(-value.im * sign, value.re * sign)
+}
+
+#[inline(always)]
+pub fn rearrange<T>(data: &mut [T], n: usize) {
     let mut j = 0;
     for i in 0..n {
         if j > i {
@@ -73,11 +433,10 @@ fn rearrange<T>(data: &mut [Complex<T>], n: usize) {
 }
 
 #[inline(always)]
-fn scale<T>(data: &mut [Complex<T>], n: usize)
+fn scale<T>(data: &mut [Complex<T>], factor: T)
 where
     T: Float,
 {
-    let factor = T::from(n).unwrap().recip();
     for value in data {
         *value = value.scale(factor);
     }
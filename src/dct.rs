@@ -0,0 +1,321 @@
+//! Discrete cosine transforms.
+//!
+//! The module provides the four common types of the [discrete cosine
+//! transform][1]. Given `n` real points `x`, the unnormalized transforms are
+//! defined as follows:
+//!
+//! * type I: `X[k] = (x[0] + (-1)^k x[n - 1]) / 2 + sum(x[j] cos(π j k / (n -
+//!   1)), 0 < j < n - 1)`,
+//! * type II: `X[k] = sum(x[j] cos(π (j + 1/2) k / n))`,
+//! * type III: `X[k] = x[0] / 2 + sum(x[j] cos(π j (k + 1/2) / n), j > 0)`,
+//! * type IV: `X[k] = sum(x[j] cos(π (j + 1/2) (k + 1/2) / n))`.
+//!
+//! Types II and III are inverses of each other up to a factor of `2 / n`, and
+//! types I and IV are their own inverses up to factors of `2 / (n - 1)` and `2
+//! / n`, respectively. With `Normalization::Orthonormal`, the transforms are
+//! scaled so that each of them is an orthogonal matrix, which makes them
+//! inverses of each other without any further scaling. The other
+//! normalizations leave the transforms unscaled.
+//!
+//! All the transforms are computed in `O(n log n)` operations using the real
+//! and complex transforms of the crate.
+//!
+//! ## Example
+//!
+//! ```
+//! use dft::dct::{Plan, Type};
+//!
+//! let plan = Plan::new(Type::II, 512);
+//! let mut data = vec![42.0; 512];
+//! plan.transform(&mut data);
+//! ```
+//!
+//! [1]: https://en.wikipedia.org/wiki/Discrete_cosine_transform
+
+use num_complex::Complex;
+use num_traits::{Float, FloatConst};
+
+use real::{try_c2r, try_r2c};
+use {twiddle, Error, Normalization, Operation, Options, Result, Transform};
+
+/// A type of transform.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Type {
+    /// Type I.
+    I,
+    /// Type II.
+    II,
+    /// Type III.
+    III,
+    /// Type IV.
+    IV,
+}
+
+/// A transform plan.
+#[derive(Clone, Debug)]
+pub struct Plan<T> {
+    kind: Type,
+    n: usize,
+    normalization: Normalization,
+    inner: ::Plan<T>,
+    factors: Vec<Complex<T>>,
+}
+
+impl<T> Plan<T>
+where
+    T: Float + FloatConst + 'static,
+{
+    /// Create a plan for a specific type and specific number of points.
+    ///
+    /// The function panics if the number of points is not supported; see
+    /// `Plan::try_new`.
+    #[inline]
+    pub fn new(kind: Type, n: usize) -> Self {
+        Plan::with_normalization(kind, n, Normalization::None)
+    }
+
+    /// Create a plan for a specific type and specific number of points if the
+    /// number of points is supported.
+    #[inline]
+    pub fn try_new(kind: Type, n: usize) -> Result<Self> {
+        Plan::try_with_normalization(kind, n, Normalization::None)
+    }
+
+    /// Create a plan for a specific type, specific number of points, and
+    /// specific normalization.
+    ///
+    /// Only `Normalization::Orthonormal` scales the transform; the other
+    /// normalizations leave it unscaled as `Plan::new` does.
+    ///
+    /// The function panics if the number of points is not supported; see
+    /// `Plan::try_with_normalization`.
+    pub fn with_normalization(kind: Type, n: usize, normalization: Normalization) -> Self {
+        match Plan::try_with_normalization(kind, n, normalization) {
+            Ok(plan) => plan,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Create a plan for a specific type, specific number of points, and
+    /// specific normalization if the number of points is supported.
+    ///
+    /// Type I requires at least two points; the other types require at least
+    /// one.
+    pub fn try_with_normalization(
+        kind: Type,
+        n: usize,
+        normalization: Normalization,
+    ) -> Result<Self> {
+        let forward = Operation::Forward;
+        let real = |operation, n| {
+            let options = Options {
+                real: true,
+                ..Options::default()
+            };
+            ::Plan::try_with_options(operation, n, options)
+        };
+        let (inner, factors) = match kind {
+            Type::I => {
+                if n < 2 {
+                    return Err(Error::UnsupportedLength(n));
+                }
+                (real(forward, 2 * (n - 1))?, vec![])
+            }
+            Type::II => (
+                real(forward, n)?,
+                (0..((n >> 1) + 1))
+                    .map(|k| twiddle(k, 4 * n, forward))
+                    .collect(),
+            ),
+            Type::III => (
+                real(Operation::Backward, n)?,
+                (0..((n >> 1) + 1))
+                    .map(|k| twiddle(k, 4 * n, Operation::Backward))
+                    .collect(),
+            ),
+            Type::IV if n & 1 == 0 => {
+                let m = n >> 1;
+                let factors = (0..m)
+                    .map(|j| twiddle(4 * j + 1, 8 * n, forward))
+                    .chain((0..m).map(|k| twiddle(k, 2 * n, forward)))
+                    .collect();
+                (::Plan::try_new(forward, m)?, factors)
+            }
+            Type::IV => {
+                let factors = (0..n)
+                    .map(|j| twiddle(j, 4 * n, forward))
+                    .chain((0..n).map(|k| twiddle(2 * k + 1, 8 * n, forward)))
+                    .collect();
+                (::Plan::try_new(forward, 2 * n)?, factors)
+            }
+        };
+        Ok(Plan {
+            kind,
+            n,
+            normalization,
+            inner,
+            factors,
+        })
+    }
+
+    /// Perform the transform.
+    ///
+    /// The function panics if the data are not compatible with the plan.
+    pub fn transform(&self, data: &mut [T]) {
+        if let Err(error) = self.try_transform(data) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Perform the transform if the data are compatible with the plan.
+    pub fn try_transform(&self, data: &mut [T]) -> Result<()> {
+        let n = self.n;
+        if data.len() != n {
+            return Err(Error::LengthMismatch {
+                plan: n,
+                data: data.len(),
+            });
+        }
+        let orthonormal = self.normalization == Normalization::Orthonormal;
+        let one = T::one();
+        let two = one + one;
+        let (root, half) = (two.sqrt(), two.recip());
+        match self.kind {
+            Type::I => {
+                if orthonormal {
+                    data[0] = data[0] * root;
+                    data[n - 1] = data[n - 1] * root;
+                }
+                let mut buffer = data
+                    .iter()
+                    .chain(data[1..(n - 1)].iter().rev())
+                    .cloned()
+                    .collect::<Vec<_>>();
+                buffer.try_transform(&self.inner)?;
+                data[0] = buffer[0] * half;
+                data[n - 1] = buffer[1] * half;
+                for k in 1..(n - 1) {
+                    data[k] = buffer[2 * k] * half;
+                }
+                if orthonormal {
+                    data[0] = data[0] * half.sqrt();
+                    data[n - 1] = data[n - 1] * half.sqrt();
+                    scale(data, (two / T::from(n - 1).unwrap()).sqrt());
+                }
+            }
+            Type::II => {
+                let h = n >> 1;
+                let buffer = fold(data);
+                let mut spectrum = vec![Complex::new(T::zero(), T::zero()); h + 1];
+                try_r2c(&buffer, &mut spectrum, &self.inner)?;
+                for (k, (&value, &factor)) in spectrum.iter().zip(&self.factors).enumerate() {
+                    let value = value * factor;
+                    data[k] = value.re;
+                    if k > 0 && n - k > k {
+                        data[n - k] = -value.im;
+                    }
+                }
+                if orthonormal {
+                    scale(data, (two / T::from(n).unwrap()).sqrt());
+                    data[0] = data[0] * half.sqrt();
+                }
+            }
+            Type::III => {
+                let h = n >> 1;
+                if orthonormal {
+                    data[0] = data[0] * root;
+                }
+                let spectrum = (0..(h + 1))
+                    .map(|k| {
+                        let other = if k > 0 { data[n - k] } else { T::zero() };
+                        Complex::new(data[k], -other) * self.factors[k]
+                    })
+                    .collect::<Vec<_>>();
+                let mut buffer = vec![T::zero(); n];
+                try_c2r(&spectrum, &mut buffer, &self.inner)?;
+                unfold(&buffer, data);
+                if orthonormal {
+                    scale(data, half * (two / T::from(n).unwrap()).sqrt());
+                } else {
+                    scale(data, half);
+                }
+            }
+            Type::IV if n & 1 == 0 => {
+                let m = n >> 1;
+                let mut buffer = (0..m)
+                    .map(|j| Complex::new(data[2 * j], data[n - 1 - 2 * j]) * self.factors[j])
+                    .collect::<Vec<_>>();
+                buffer.try_transform(&self.inner)?;
+                for (k, (&value, &factor)) in buffer.iter().zip(&self.factors[m..]).enumerate() {
+                    let value = value * factor;
+                    data[2 * k] = value.re;
+                    data[n - 1 - 2 * k] = -value.im;
+                }
+                if orthonormal {
+                    scale(data, (two / T::from(n).unwrap()).sqrt());
+                }
+            }
+            Type::IV => {
+                let mut buffer = vec![Complex::new(T::zero(), T::zero()); 2 * n];
+                for (value, (&datum, &factor)) in
+                    buffer.iter_mut().zip(data.iter().zip(&self.factors))
+                {
+                    *value = factor.scale(datum);
+                }
+                buffer.try_transform(&self.inner)?;
+                for (value, (&other, &factor)) in
+                    data.iter_mut().zip(buffer.iter().zip(&self.factors[n..]))
+                {
+                    *value = (other * factor).re;
+                }
+                if orthonormal {
+                    scale(data, (two / T::from(n).unwrap()).sqrt());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Interleave the even-indexed points with the odd-indexed ones in reverse
+/// order.
+fn fold<T>(data: &[T]) -> Vec<T>
+where
+    T: Float,
+{
+    let n = data.len();
+    let mut buffer = vec![T::zero(); n];
+    for (j, &value) in data.iter().enumerate() {
+        if j & 1 == 0 {
+            buffer[j >> 1] = value;
+        } else {
+            buffer[n - 1 - (j >> 1)] = value;
+        }
+    }
+    buffer
+}
+
+/// Undo `fold`.
+fn unfold<T>(buffer: &[T], data: &mut [T])
+where
+    T: Float,
+{
+    let n = data.len();
+    for (j, value) in data.iter_mut().enumerate() {
+        *value = if j & 1 == 0 {
+            buffer[j >> 1]
+        } else {
+            buffer[n - 1 - (j >> 1)]
+        };
+    }
+}
+
+#[inline(always)]
+fn scale<T>(data: &mut [T], factor: T)
+where
+    T: Float,
+{
+    for value in data {
+        *value = *value * factor;
+    }
+}
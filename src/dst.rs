@@ -0,0 +1,168 @@
+//! Discrete sine transforms.
+//!
+//! The module provides the four common types of the [discrete sine
+//! transform][1]. Given `n` real points `x`, the unnormalized transforms are
+//! defined as follows:
+//!
+//! * type I: `X[k] = sum(x[j] sin(π (j + 1) (k + 1) / (n + 1)))`,
+//! * type II: `X[k] = sum(x[j] sin(π (j + 1/2) (k + 1) / n))`,
+//! * type III: `X[k] = (-1)^k x[n - 1] / 2 + sum(x[j] sin(π (j + 1) (k + 1/2)
+//!   / n), j < n - 1)`,
+//! * type IV: `X[k] = sum(x[j] sin(π (j + 1/2) (k + 1/2) / n))`.
+//!
+//! Types II and III are inverses of each other up to a factor of `2 / n`, and
+//! types I and IV are their own inverses up to factors of `2 / (n + 1)` and `2
+//! / n`, respectively. The normalizations are the same as for the cosine
+//! transforms; see the `dct` module. Type I is computed via the real
+//! transform, and the other types via the corresponding cosine transforms.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Discrete_sine_transform
+
+use num_traits::{Float, FloatConst};
+
+use dct;
+use {Error, Normalization, Operation, Options, Result, Transform};
+
+pub use dct::Type;
+
+/// A transform plan.
+#[derive(Clone, Debug)]
+pub struct Plan<T> {
+    kind: Type,
+    n: usize,
+    normalization: Normalization,
+    algorithm: Algorithm<T>,
+}
+
+#[derive(Clone, Debug)]
+enum Algorithm<T> {
+    Real(::Plan<T>),
+    Cosine(dct::Plan<T>),
+}
+
+impl<T> Plan<T>
+where
+    T: Float + FloatConst + 'static,
+{
+    /// Create a plan for a specific type and specific number of points.
+    ///
+    /// The function panics if the number of points is not supported; see
+    /// `Plan::try_new`.
+    #[inline]
+    pub fn new(kind: Type, n: usize) -> Self {
+        Plan::with_normalization(kind, n, Normalization::None)
+    }
+
+    /// Create a plan for a specific type and specific number of points if the
+    /// number of points is supported.
+    #[inline]
+    pub fn try_new(kind: Type, n: usize) -> Result<Self> {
+        Plan::try_with_normalization(kind, n, Normalization::None)
+    }
+
+    /// Create a plan for a specific type, specific number of points, and
+    /// specific normalization.
+    ///
+    /// Only `Normalization::Orthonormal` scales the transform; the other
+    /// normalizations leave it unscaled as `Plan::new` does.
+    ///
+    /// The function panics if the number of points is not supported; see
+    /// `Plan::try_with_normalization`.
+    pub fn with_normalization(kind: Type, n: usize, normalization: Normalization) -> Self {
+        match Plan::try_with_normalization(kind, n, normalization) {
+            Ok(plan) => plan,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Create a plan for a specific type, specific number of points, and
+    /// specific normalization if the number of points is supported.
+    pub fn try_with_normalization(
+        kind: Type,
+        n: usize,
+        normalization: Normalization,
+    ) -> Result<Self> {
+        if n == 0 {
+            return Err(Error::UnsupportedLength(n));
+        }
+        let algorithm = match kind {
+            Type::I => Algorithm::Real(::Plan::try_with_options(
+                Operation::Forward,
+                2 * (n + 1),
+                Options {
+                    real: true,
+                    ..Options::default()
+                },
+            )?),
+            _ => Algorithm::Cosine(dct::Plan::try_with_normalization(kind, n, normalization)?),
+        };
+        Ok(Plan {
+            kind,
+            n,
+            normalization,
+            algorithm,
+        })
+    }
+
+    /// Perform the transform.
+    ///
+    /// The function panics if the data are not compatible with the plan.
+    pub fn transform(&self, data: &mut [T]) {
+        if let Err(error) = self.try_transform(data) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Perform the transform if the data are compatible with the plan.
+    pub fn try_transform(&self, data: &mut [T]) -> Result<()> {
+        let n = self.n;
+        if data.len() != n {
+            return Err(Error::LengthMismatch {
+                plan: n,
+                data: data.len(),
+            });
+        }
+        match self.algorithm {
+            Algorithm::Real(ref plan) => {
+                let mut buffer = vec![T::zero(); 2 * (n + 1)];
+                for (j, &value) in data.iter().enumerate() {
+                    buffer[j + 1] = value;
+                    buffer[2 * n + 1 - j] = -value;
+                }
+                buffer.try_transform(plan)?;
+                let one = T::one();
+                let mut factor = -(one + one).recip();
+                if self.normalization == Normalization::Orthonormal {
+                    factor = factor * ((one + one) / T::from(n + 1).unwrap()).sqrt();
+                }
+                for (k, value) in data.iter_mut().enumerate() {
+                    *value = buffer[2 * k + 3] * factor;
+                }
+            }
+            Algorithm::Cosine(ref plan) => {
+                if self.kind == Type::II {
+                    negate(data);
+                } else {
+                    data.reverse();
+                }
+                plan.try_transform(data)?;
+                if self.kind == Type::II {
+                    data.reverse();
+                } else {
+                    negate(data);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Negate the odd-indexed points.
+fn negate<T>(data: &mut [T])
+where
+    T: Float,
+{
+    for value in data.iter_mut().skip(1).step_by(2) {
+        *value = -*value;
+    }
+}
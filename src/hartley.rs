@@ -0,0 +1,90 @@
+use num_complex::Complex;
+use num_traits::{Float, FloatConst};
+
+use real::{try_c2r, try_r2c};
+use {Error, Operation, Plan, Result};
+
+/// Perform the discrete Hartley transform of real data in place.
+///
+/// The transform is defined as `H[k] = sum(x[j] cas(2π j k / n))`, where
+/// `cas(θ) = cos(θ) + sin(θ)`, and it is its own inverse up to a factor of `1 /
+/// n`. The result is computed via the real transform and is the same for all
+/// operations of the plan except for scaling, which follows the normalization
+/// of the plan; in particular, applying the function with a forward plan and
+/// then with an inverse plan recovers the data.
+///
+/// The function panics if the data are not compatible with the plan; see
+/// `try_hartley`.
+pub fn hartley<T>(data: &mut [T], plan: &Plan<T>)
+where
+    T: Float + FloatConst + 'static,
+{
+    if let Err(error) = try_hartley(data, plan) {
+        panic!("{}", error);
+    }
+}
+
+/// Perform the discrete Hartley transform of real data in place if the data
+/// are compatible with the plan.
+///
+/// The function is the fallible counterpart of `hartley`.
+pub fn try_hartley<T>(data: &mut [T], plan: &Plan<T>) -> Result<()>
+where
+    T: Float + FloatConst + 'static,
+{
+    let n = data.len();
+    if n != plan.n {
+        return Err(Error::LengthMismatch {
+            plan: plan.n,
+            data: n,
+        });
+    }
+    let h = n >> 1;
+    let mut spectrum = vec![Complex::new(T::zero(), T::zero()); h + 1];
+    if plan.operation == Operation::Forward {
+        try_r2c(data, &mut spectrum, plan)?;
+        for (k, value) in spectrum.iter().enumerate() {
+            data[k] = value.re - value.im;
+            if k > 0 {
+                data[n - k] = value.re + value.im;
+            }
+        }
+    } else {
+        let half = (T::one() + T::one()).recip();
+        for (k, value) in spectrum.iter_mut().enumerate() {
+            let (first, second) = (data[k], data[(n - k) % n]);
+            *value = Complex::new(first + second, second - first).scale(half);
+        }
+        try_c2r(&spectrum, data, plan)?;
+    }
+    Ok(())
+}
+
+/// Convert the result of the Hartley transform into a complex spectrum.
+///
+/// The function produces the same spectrum as `unpack` applied to the result
+/// of the forward transform of the same real data.
+pub fn unpack_hartley<T>(data: &[T]) -> Vec<Complex<T>>
+where
+    T: Float,
+{
+    let n = data.len();
+    let half = (T::one() + T::one()).recip();
+    (0..n)
+        .map(|k| {
+            let (first, second) = (data[k], data[(n - k) % n]);
+            Complex::new(first + second, second - first).scale(half)
+        })
+        .collect()
+}
+
+/// Convert a complex spectrum into the result of the Hartley transform.
+///
+/// The function is the inverse of `unpack_hartley`. The spectrum is assumed
+/// to be that of real data, in which case the result is real.
+pub fn pack_hartley<T>(data: &[Complex<T>]) -> Vec<T>
+where
+    T: Float,
+{
+    data.iter().map(|value| value.re - value.im).collect()
+}
@@ -5,15 +5,55 @@
 //! operations available: forward, backward, and inverse. The desired operation
 //! is specified by the `Operation` enumeration passed to the `Plan::new`
 //! function, which precomputes auxiliary information needed for
-//! `Transform::transform`. All the operations are preformed in place.
+//! `Transform::transform`. All the operations are preformed in place. The
+//! functions panic when given unsupported input; their counterparts
+//! `Plan::try_new` and `Transform::try_transform` return an `Error` instead.
+//! The trait is also implemented for `Strided`, a view of every `k`th element
+//! of a slice, such as one channel of an interleaved signal.
+//!
+//! By default, only the inverse operation scales the result, by `1 / n`. Other
+//! scaling conventions can be chosen via the `Normalization` field of the
+//! `Options` passed to `Plan::with_options`. The same options allow for
+//! computing the twiddle factors for powers of two directly in double
+//! precision, which is slower to plan but more accurate for large transforms,
+//! and for selecting the Stockham kernel, which works out of place using a
+//! scratch buffer given to `transform_with_scratch`.
+//!
+//! Complex data can be of any length. Powers of two are handled by radix-4 and
+//! split-radix algorithms, lengths with no prime factors other than 2, 3, 5,
+//! and 7 by a mixed-radix one, suitable prime lengths by Rader’s algorithm, and
+//! all other lengths by Bluestein’s algorithm. Above a given size, composite
+//! lengths can also be handed over to Bailey’s four-step algorithm. On x86-64,
+//! the butterflies for powers of two are vectorized for `f32` and `f64` using
+//! the best SIMD instructions detected at run time; see `Simd`. With the
+//! `parallel` feature, large transforms for powers of two can also be
+//! distributed across threads; see `Options`. Real data can be of any length as
+//! well. When the number of points is a power of two, the plan can also be
+//! applied to complex data whose length is a smaller power of two.
+//! Two-dimensional complex data stored in row-major order are handled by
+//! `Plan2`, which also provides a real transform storing only half of the
+//! spectrum, and data of any shape are handled by `PlanN`, which transforms
+//! along a chosen subset of the axes. Discrete cosine and sine
+//! transforms are provided by the `dct` and `dst` modules, respectively, and
+//! the modified discrete cosine transform with overlapping windows by `Mdct`.
+//! The discrete Hartley transform of real data is performed by `hartley`, and
+//! exact transforms of integers modulo a prime are provided by the `ntt`
+//! module.
 //!
 //! When applied to real data, the transform works as follows. If the operation
 //! is forward, the data are replaced by the positive frequency half of their
-//! complex transform. The first and last components of the complex transform,
-//! which are real, are stored in `self[0]` and `self[1]`, respectively. If the
-//! operation is backward or inverse, the data are assumed to be stored
-//! according to the above convention. See the reference below for further
-//! details.
+//! complex transform. If the number of points is even, the first and last
+//! components of the complex transform, which are real, are stored in `self[0]`
+//! and `self[1]`, respectively, and the real and imaginary parts of the `k`th
+//! component are stored in `self[2 * k]` and `self[2 * k + 1]`. If the number
+//! of points is odd, there is no last real component; the first component is
+//! stored in `self[0]`, and the real and imaginary parts of the `k`th component
+//! are stored in `self[2 * k - 1]` and `self[2 * k]`. If the operation is
+//! backward or inverse, the data are assumed to be stored according to the
+//! above convention. As for complex data, the backward operation applied to
+//! the result of the forward one yields the original data multiplied by `n`;
+//! note that versions before 0.6 multiplied real data by `n / 2` instead. See
+//! the reference below for further details.
 //!
 //! ## Example
 //!
@@ -28,8 +68,8 @@
 //! ## References
 //!
 //! 1. W. Press, S. Teukolsky, W. Vetterling, and B. Flannery, “Numerical
-//! Recipes 3rd Edition: The Art of Scientific Computing,” Cambridge University
-//! Press, 2007.
+//!    Recipes 3rd Edition: The Art of Scientific Computing,” Cambridge
+//!    University Press, 2007.
 //!
 //! [1]: https://en.wikipedia.org/wiki/Discrete_Fourier_transform
 
@@ -38,6 +78,8 @@ extern crate num_traits;
 
 use num_complex::Complex;
 use num_traits::{Float, FloatConst, One};
+use std::sync::OnceLock;
+use std::{error, fmt};
 
 /// A complex number with 32-bit parts.
 #[allow(non_camel_case_types)]
@@ -47,10 +89,78 @@ pub type c32 = Complex<f32>;
 #[allow(non_camel_case_types)]
 pub type c64 = Complex<f64>;
 
+mod bailey;
+mod bluestein;
 mod complex;
+pub mod dct;
+pub mod dst;
+mod hartley;
+mod mdct;
+mod mixed;
+pub mod ntt;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod plan2;
+mod plann;
+mod rader;
 mod real;
+#[cfg(target_arch = "x86_64")]
+mod simd;
+mod strided;
+
+pub use complex::{transform_with_scratch, try_transform_with_scratch};
+pub use hartley::{hartley, pack_hartley, try_hartley, unpack_hartley};
+pub use mdct::{Mdct, Window};
+pub use plan2::{unpack2, Plan2};
+pub use plann::PlanN;
+pub use real::{
+    c2r, pack, pack_into, r2c, try_c2r, try_r2c, unpack, unpack_half_into, unpack_into,
+};
+pub use strided::Strided;
+
+/// An error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The number of points is not supported.
+    UnsupportedLength(usize),
+    /// The length of the data does not match the number of points of the plan.
+    LengthMismatch {
+        /// The number of points of the plan.
+        plan: usize,
+        /// The length of the data.
+        data: usize,
+    },
+    /// The operation is not supported for the kind of data given.
+    OperationMismatch(Operation),
+    /// The scratch buffer is shorter than the data.
+    ScratchTooShort {
+        /// The length of the data.
+        data: usize,
+        /// The length of the scratch buffer.
+        scratch: usize,
+    },
+    /// The modulus is not supported.
+    UnsupportedModulus(u64),
+    /// The axis does not exist in the shape.
+    UnsupportedAxis {
+        /// The axis.
+        axis: usize,
+        /// The number of dimensions of the shape.
+        dimensions: usize,
+    },
+    /// The stride of the view is not supported.
+    UnsupportedStride(usize),
+    /// The view does not fit in the data.
+    ViewOutOfBounds {
+        /// The number of elements the view spans from the start of the data.
+        view: usize,
+        /// The length of the data.
+        data: usize,
+    },
+}
 
-pub use real::unpack;
+/// A result.
+pub type Result<T> = std::result::Result<T, Error>;
 
 /// A transform operation.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -63,32 +173,272 @@ pub enum Operation {
     Inverse,
 }
 
+/// A normalization.
+///
+/// The normalization determines how the result of a transform is scaled. The
+/// backward operation is never scaled. The cosine and sine transforms are
+/// scaled only by `Normalization::Orthonormal`; see the `dct` module.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Normalization {
+    /// Scale the inverse transform by `1 / n` (the default).
+    #[default]
+    Inverse,
+    /// Scale the forward and inverse transforms by `1 / sqrt(n)`.
+    Orthonormal,
+    /// Scale the forward transform by `1 / n`.
+    Forward,
+    /// Do not scale.
+    None,
+}
+
+/// A method of computing twiddle factors.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Twiddles {
+    /// Compute the factors for powers of two using a trigonometric
+    /// recurrence (the default).
+    ///
+    /// The recurrence is fast, but its error grows with the number of points.
+    #[default]
+    Recurrence,
+    /// Compute each factor directly in double precision.
+    Direct,
+}
+
+/// A kernel for powers of two.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Kernel {
+    /// Permute the data in bit-reversed order and then perform the butterflies
+    /// in place (the default).
+    #[default]
+    InPlace,
+    /// Perform the butterflies alternating between the data and a scratch
+    /// buffer so that the result comes out in natural order.
+    ///
+    /// The kernel avoids the bit-reversal permutation. The scratch buffer is
+    /// allocated on each call unless it is given to `transform_with_scratch`.
+    Stockham,
+}
+
+/// A set of SIMD instructions for powers of two.
+///
+/// The instructions are used only for `f32` and `f64` on x86-64 and only if
+/// they are available; otherwise, the scalar code is used.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Simd {
+    /// Detect the best instructions available at run time (the default).
+    #[default]
+    Detect,
+    /// Use no SIMD instructions.
+    None,
+    /// Use SSE2 instructions.
+    Sse2,
+    /// Use AVX instructions.
+    Avx,
+    /// Use AVX2 and FMA instructions.
+    Avx2,
+}
+
+/// Plan options.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Options {
+    /// The normalization.
+    pub normalization: Normalization,
+    /// The method of computing twiddle factors.
+    pub twiddles: Twiddles,
+    /// The kernel for powers of two.
+    pub kernel: Kernel,
+    /// The SIMD instructions for powers of two.
+    pub simd: Simd,
+    /// The number of points above which composite lengths are handled by
+    /// Bailey’s four-step algorithm (`None` by default, which disables it).
+    ///
+    /// The algorithm splits the transform into two series of smaller ones,
+    /// which fit in the cache, and allocates a buffer of the size of the data
+    /// on each call. Unlike other plans for powers of two, the resulting plans
+    /// cannot be applied to shorter data.
+    pub four_step: Option<usize>,
+    /// The number of threads for large powers of two.
+    ///
+    /// The option has an effect only for `f32` and `f64` and only with the
+    /// `parallel` feature. Zero and one mean the calling thread only.
+    pub threads: usize,
+    /// Prepare the plan for real data (`false` by default).
+    ///
+    /// Real data of an even length that is not a power of two are transformed
+    /// using a plan for half the number of points. With the option, the plan
+    /// is built along with the main one; without it, the plan is built on
+    /// first use with real data so that plans for complex data stay cheap.
+    pub real: bool,
+}
+
 /// A transform plan.
 #[derive(Clone, Debug)]
 pub struct Plan<T> {
     n: usize,
     factors: Vec<Complex<T>>,
     operation: Operation,
+    options: Options,
+    algorithm: Algorithm<T>,
+    half: OnceLock<Box<Half<T>>>,
+}
+
+/// The auxiliary information for real data of an even length that is not a
+/// power of two.
+#[derive(Clone, Debug)]
+struct Half<T> {
+    factors: Vec<Complex<T>>,
+    plan: Plan<T>,
+}
+
+#[derive(Clone, Debug)]
+enum Algorithm<T> {
+    Radix2,
+    Bailey(Box<bailey::Bailey<T>>),
+    Mixed(mixed::Mixed<T>),
+    Bluestein(Box<bluestein::Bluestein<T>>),
+    Rader(Box<rader::Rader<T>>),
 }
 
 /// The transform.
 pub trait Transform<T> {
     /// Perform the transform.
-    fn transform(&mut self, &Plan<T>);
+    ///
+    /// The function panics if the data are not compatible with the plan.
+    fn transform(&mut self, plan: &Plan<T>) {
+        if let Err(error) = self.try_transform(plan) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Perform the transform if the data are compatible with the plan.
+    fn try_transform(&mut self, plan: &Plan<T>) -> Result<()>;
+
+    /// Perform the transform if the data are compatible with the plan using a
+    /// buffer that is extended as needed and can be reused across calls.
+    #[doc(hidden)]
+    fn try_transform_with_buffer(
+        &mut self,
+        plan: &Plan<T>,
+        buffer: &mut Vec<Complex<T>>,
+    ) -> Result<()> {
+        let _ = buffer;
+        self.try_transform(plan)
+    }
 }
 
 impl<T> Plan<T>
 where
-    T: Float + FloatConst,
+    T: Float + FloatConst + 'static,
 {
     /// Create a plan for a specific operation and specific number of points.
     ///
-    /// The number of points should be a power of two.
+    /// Powers of two are handled by a split-radix algorithm, numbers with no
+    /// prime factors other than 2, 3, 5, and 7 by a mixed-radix one, primes
+    /// whose predecessors have no such factors by Rader’s algorithm, and all
+    /// other numbers by Bluestein’s algorithm.
+    ///
+    /// The function panics if the number of points is not supported; see
+    /// `Plan::try_new`.
+    #[inline]
     pub fn new(operation: Operation, n: usize) -> Self {
-        assert!(n.is_power_of_two());
+        Plan::with_options(operation, n, Options::default())
+    }
+
+    /// Create a plan for a specific operation and specific number of points if
+    /// the number of points is supported.
+    ///
+    /// The unsupported numbers of points are zero, those greater than
+    /// `usize::MAX / 4 + 1`, and those whose precomputed tables do not fit in
+    /// memory.
+    #[inline]
+    pub fn try_new(operation: Operation, n: usize) -> Result<Self> {
+        Plan::try_with_options(operation, n, Options::default())
+    }
+
+    /// Create a plan for a specific operation, specific number of points, and
+    /// specific options.
+    ///
+    /// The function panics if the number of points is not supported; see
+    /// `Plan::try_with_options`.
+    pub fn with_options(operation: Operation, n: usize, options: Options) -> Self {
+        match Plan::try_with_options(operation, n, options) {
+            Ok(plan) => plan,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Create a plan for a specific operation, specific number of points, and
+    /// specific options if the number of points is supported.
+    pub fn try_with_options(operation: Operation, n: usize, options: Options) -> Result<Self> {
+        // Bluestein’s algorithm pads the data to a power of two not smaller
+        // than `2 * n - 1`, which should fit in `usize`.
+        if n == 0 || n > usize::MAX / 4 + 1 {
+            return Err(Error::UnsupportedLength(n));
+        }
+        let plan = match Plan::complex(operation, n, options) {
+            Ok(plan) => plan,
+            _ => return Err(Error::UnsupportedLength(n)),
+        };
+        if options.real && n & 1 == 0 && plan.half().is_err() {
+            return Err(Error::UnsupportedLength(n));
+        }
+        Ok(plan)
+    }
+
+    fn complex(operation: Operation, n: usize, options: Options) -> Result<Self> {
+        let split = match options.four_step {
+            Some(threshold) if n > threshold => bailey::split(n),
+            _ => None,
+        };
+        if let Some((n1, n2)) = split {
+            return Ok(Plan {
+                n,
+                factors: vec![],
+                operation,
+                options,
+                algorithm: Algorithm::Bailey(Box::new(bailey::Bailey::new(
+                    operation, n1, n2, options,
+                )?)),
+                half: OnceLock::new(),
+            });
+        }
+        if !n.is_power_of_two() {
+            let algorithm = match mixed::factorize(n) {
+                Some(radices) => Algorithm::Mixed(mixed::Mixed::new(operation, radices)?),
+                _ if rader::suitable(n) => {
+                    Algorithm::Rader(Box::new(rader::Rader::new(operation, n, options)?))
+                }
+                _ => Algorithm::Bluestein(Box::new(bluestein::Bluestein::new(
+                    operation, n, options,
+                )?)),
+            };
+            return Ok(Plan {
+                n,
+                factors: vec![],
+                operation,
+                options,
+                algorithm,
+                half: OnceLock::new(),
+            });
+        }
+        let mut factors = table(n - 1)?;
+        if let Twiddles::Direct = options.twiddles {
+            let mut step = 1;
+            while step < n {
+                factors.extend((0..step).map(|k| twiddle(k, step << 1, operation)));
+                step <<= 1;
+            }
+            return Ok(Plan {
+                n,
+                factors,
+                operation,
+                options,
+                algorithm: Algorithm::Radix2,
+                half: OnceLock::new(),
+            });
+        }
         let one = T::one();
         let two = one + one;
-        let mut factors = vec![];
         let sign = if let Operation::Forward = operation {
             -one
         } else {
@@ -110,21 +460,280 @@ where
             }
             step <<= 1;
         }
-        Plan {
-            n: n,
-            factors: factors,
-            operation: operation,
+        Ok(Plan {
+            n,
+            factors,
+            operation,
+            options,
+            algorithm: Algorithm::Radix2,
+            half: OnceLock::new(),
+        })
+    }
+
+    /// Return the factors for composing the transform of real data of an even
+    /// length and the plan for half the number of points.
+    ///
+    /// For powers of two, the plan itself serves both purposes; otherwise, the
+    /// two are built on first use unless the `real` option has done so.
+    fn half(&self) -> Result<(&[Complex<T>], &Plan<T>)> {
+        if let Algorithm::Radix2 = self.algorithm {
+            return Ok((&self.factors, self));
+        }
+        if self.half.get().is_none() {
+            let (n, operation) = (self.n, self.operation);
+            let mut factors = table(n >> 1)?;
+            factors.extend((0..(n >> 1)).map(|k| twiddle(k, n, operation)));
+            let plan = Plan::complex(operation, n >> 1, self.options)?;
+            let _ = self.half.set(Box::new(Half { factors, plan }));
         }
+        let half = self.half.get().unwrap();
+        Ok((&half.factors, &half.plan))
     }
 }
 
+impl<T> Plan<T>
+where
+    T: Float,
+{
+    /// Perform the transform of a batch of signals.
+    ///
+    /// The `i`th signal occupies `data[(i * distance)..(i * distance + n)]`
+    /// where `n` is the number of points of the plan. The data can be either
+    /// complex or real. The scratch memory needed by the plan is allocated
+    /// once for the whole batch. The function panics if the data are not
+    /// compatible with the plan; see `Plan::try_transform_batch`.
+    pub fn transform_batch<D>(&self, data: &mut [D], count: usize, distance: usize)
+    where
+        [D]: Transform<T>,
+    {
+        if let Err(error) = self.try_transform_batch(data, count, distance) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Perform the transform of a batch of signals if the data are compatible
+    /// with the plan.
+    ///
+    /// The signals must not overlap, that is, the distance must not be smaller
+    /// than the number of points, and the data must hold all of them.
+    pub fn try_transform_batch<D>(
+        &self,
+        data: &mut [D],
+        count: usize,
+        distance: usize,
+    ) -> Result<()>
+    where
+        [D]: Transform<T>,
+    {
+        let n = self.n;
+        if count == 0 {
+            return Ok(());
+        }
+        if distance < n {
+            return Err(Error::LengthMismatch {
+                plan: n,
+                data: distance,
+            });
+        }
+        let required = (count - 1)
+            .checked_mul(distance)
+            .and_then(|length| length.checked_add(n));
+        if required.is_none_or(|required| data.len() < required) {
+            return Err(Error::LengthMismatch {
+                plan: n,
+                data: data.len(),
+            });
+        }
+        let mut buffer = vec![];
+        for chunk in data.chunks_mut(distance).take(count) {
+            chunk[..n].try_transform_with_buffer(self, &mut buffer)?;
+        }
+        Ok(())
+    }
+
+    fn factor(&self, n: usize) -> Option<T> {
+        let inverse = || T::from(n).unwrap().recip();
+        match (self.options.normalization, self.operation) {
+            (Normalization::Inverse, Operation::Inverse) => Some(inverse()),
+            (Normalization::Orthonormal, Operation::Forward)
+            | (Normalization::Orthonormal, Operation::Inverse) => Some(inverse().sqrt()),
+            (Normalization::Forward, Operation::Forward) => Some(inverse()),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Plan<T> {
+    /// Return the length of the scratch buffer needed by the complex transform.
+    fn scratch(&self) -> usize {
+        match self.algorithm {
+            Algorithm::Radix2 if self.options.kernel == Kernel::Stockham => self.n,
+            Algorithm::Radix2 | Algorithm::Mixed(_) => 0,
+            Algorithm::Bailey(ref bailey) => bailey.scratch(),
+            Algorithm::Bluestein(ref bluestein) => bluestein.scratch(),
+            Algorithm::Rader(ref rader) => rader.scratch(),
+        }
+    }
+}
+
+/// Allocate an empty table of a specific capacity.
+///
+/// Unlike the standard allocation functions, the function reports a lack of
+/// memory as an unsupported length instead of aborting.
+fn table<U>(capacity: usize) -> Result<Vec<U>> {
+    let mut table = Vec::new();
+    match table.try_reserve_exact(capacity) {
+        Ok(()) => Ok(table),
+        _ => Err(Error::UnsupportedLength(capacity)),
+    }
+}
+
+/// Compute `exp(±2πik/n)` with the sign of the operation.
+///
+/// The factor is computed in double precision after reducing the angle to the
+/// first octant, which is exact.
+fn twiddle<T>(k: usize, n: usize, operation: Operation) -> Complex<T>
+where
+    T: Float,
+{
+    let (mut m, mut octant) = (4 * (k % n), 0);
+    let (quarter, n) = (n, 4 * n);
+    if m > n - m {
+        m = n - m;
+        octant |= 4;
+    }
+    if m > quarter {
+        m -= quarter;
+        octant |= 2;
+    }
+    if m > quarter - m {
+        m = quarter - m;
+        octant |= 1;
+    }
+    let theta = 2.0 * std::f64::consts::PI * m as f64 / n as f64;
+    let (mut cosine, mut sine) = (theta.cos(), theta.sin());
+    if octant & 1 != 0 {
+        std::mem::swap(&mut cosine, &mut sine);
+    }
+    if octant & 2 != 0 {
+        let value = cosine;
+        cosine = -sine;
+        sine = value;
+    }
+    if octant & 4 != 0 {
+        sine = -sine;
+    }
+    if let Operation::Forward = operation {
+        sine = -sine;
+    }
+    Complex::new(T::from(cosine).unwrap(), T::from(sine).unwrap())
+}
+
+/// Check if two types are the same type.
+#[cfg(any(target_arch = "x86_64", feature = "parallel"))]
+fn is<T: 'static, U: 'static>() -> bool {
+    use std::any::TypeId;
+    TypeId::of::<T>() == TypeId::of::<U>()
+}
+
 /// Perform the transform.
 ///
 /// The function is a shortcut for `Transform::transform`.
 #[inline(always)]
-pub fn transform<D: ?Sized, T>(data: &mut D, plan: &Plan<T>)
+pub fn transform<D, T>(data: &mut D, plan: &Plan<T>)
 where
-    D: Transform<T>,
+    D: Transform<T> + ?Sized,
 {
     Transform::transform(data, plan);
 }
+
+/// Perform the transform if the data are compatible with the plan.
+///
+/// The function is a shortcut for `Transform::try_transform`.
+#[inline(always)]
+pub fn try_transform<D, T>(data: &mut D, plan: &Plan<T>) -> Result<()>
+where
+    D: Transform<T> + ?Sized,
+{
+    Transform::try_transform(data, plan)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::UnsupportedLength(n) => {
+                write!(formatter, "the number of points is not supported ({})", n)
+            }
+            Error::LengthMismatch { plan, data } => write!(
+                formatter,
+                "the length of the data ({}) does not match the plan ({})",
+                data, plan,
+            ),
+            Error::OperationMismatch(operation) => write!(
+                formatter,
+                "the operation is not supported for the data ({:?})",
+                operation,
+            ),
+            Error::ScratchTooShort { data, scratch } => write!(
+                formatter,
+                "the scratch buffer ({}) is shorter than the data ({})",
+                scratch, data,
+            ),
+            Error::UnsupportedModulus(modulus) => {
+                write!(formatter, "the modulus is not supported ({})", modulus)
+            }
+            Error::UnsupportedAxis { axis, dimensions } => write!(
+                formatter,
+                "the axis ({}) does not exist in the shape ({} dimensions)",
+                axis, dimensions,
+            ),
+            Error::UnsupportedStride(stride) => {
+                write!(formatter, "the stride is not supported ({})", stride)
+            }
+            Error::ViewOutOfBounds { view, data } => write!(
+                formatter,
+                "the view ({}) does not fit in the data ({})",
+                view, data,
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use {Operation, Options, Plan, Twiddles};
+
+    #[test]
+    fn twiddles() {
+        let n = 1 << 20;
+        let error = |twiddles| {
+            let options = Options {
+                twiddles,
+                ..Options::default()
+            };
+            let plan = Plan::<f32>::with_options(Operation::Forward, n, options);
+            let mut error = 0f64;
+            let mut step = 1;
+            let mut factors = plan.factors.iter();
+            while step < n {
+                for k in 0..step {
+                    let factor = factors.next().unwrap();
+                    let angle = -PI * k as f64 / step as f64;
+                    error = error
+                        .max((factor.re as f64 - angle.cos()).abs())
+                        .max((factor.im as f64 - angle.sin()).abs());
+                }
+                step <<= 1;
+            }
+            error
+        };
+        let direct = error(Twiddles::Direct);
+        let recurrence = error(Twiddles::Recurrence);
+        assert!(direct <= f32::EPSILON as f64 / 2.0);
+        assert!(recurrence > 10.0 * direct);
+    }
+}
@@ -0,0 +1,194 @@
+// The implementation expresses the modified discrete cosine transform of 2n
+// points in terms of a type-IV discrete cosine transform of n points applied to
+// a folded frame. Splitting the frame into four quarters (a, b, c, d), the
+// transform equals that of (-c_R - d, a - b_R), where _R denotes reversal, and
+// the inverse unfolds the result accordingly:
+// https://en.wikipedia.org/wiki/Modified_discrete_cosine_transform
+
+use num_traits::{Float, FloatConst};
+
+use dct::{self, Type};
+use {Error, Result};
+
+/// A window.
+///
+/// Both windows satisfy the Princen–Bradley condition, which, when the same
+/// window is applied before the forward transform and after the inverse one,
+/// makes the overlap-add of consecutive frames reconstruct the signal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Window {
+    /// The sine window.
+    Sine,
+    /// The Kaiser–Bessel-derived window with a given parameter `α`.
+    ///
+    /// The parameter controls the trade-off between the width of the main lobe
+    /// and the attenuation of the side lobes; AAC uses `α = 4` for long frames
+    /// and `α = 6` for short ones.
+    KaiserBessel(f64),
+}
+
+/// A plan of the modified discrete cosine transform.
+///
+/// The forward transform maps a frame of `2 * n` points to `n` coefficients
+/// according to `X[k] = sum(w[j] x[j] cos(π (j + 1/2 + n/2) (k + 1/2) / n))`,
+/// where `w` is the window. The inverse transform maps `n` coefficients back to
+/// `2 * n` windowed points scaled by `2 / n` and adds them to the output, so
+/// that frames taken every `n` points are reconstructed by applying the inverse
+/// to consecutive, overlapping parts of the same buffer.
+#[derive(Clone, Debug)]
+pub struct Mdct<T> {
+    n: usize,
+    window: Vec<T>,
+    plan: dct::Plan<T>,
+}
+
+impl<T> Mdct<T>
+where
+    T: Float + FloatConst + 'static,
+{
+    /// Create a plan for a specific number of coefficients and a specific
+    /// window.
+    ///
+    /// The function panics if the number of coefficients is not supported; see
+    /// `Mdct::try_new`.
+    pub fn new(n: usize, window: Window) -> Self {
+        match Mdct::try_new(n, window) {
+            Ok(plan) => plan,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Create a plan for a specific number of coefficients and a specific
+    /// window if the number of coefficients is supported.
+    ///
+    /// The number of coefficients should be even and positive.
+    pub fn try_new(n: usize, window: Window) -> Result<Self> {
+        if n == 0 || n & 1 == 1 {
+            return Err(Error::UnsupportedLength(n));
+        }
+        let window = match window {
+            Window::Sine => sine(n),
+            Window::KaiserBessel(alpha) => kaiser_bessel(n, alpha),
+        };
+        Ok(Mdct {
+            n,
+            window: window
+                .into_iter()
+                .map(|value| T::from(value).unwrap())
+                .collect(),
+            plan: dct::Plan::try_new(Type::IV, n)?,
+        })
+    }
+
+    /// Perform the forward transform.
+    ///
+    /// The function reads a frame of `2 * n` points from `input` and writes
+    /// `n` coefficients to `output`. The function panics if the data are not
+    /// compatible with the plan; see `Mdct::try_forward`.
+    pub fn forward(&self, input: &[T], output: &mut [T]) {
+        if let Err(error) = self.try_forward(input, output) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Perform the forward transform if the data are compatible with the plan.
+    ///
+    /// The function is the fallible counterpart of `Mdct::forward`.
+    pub fn try_forward(&self, input: &[T], output: &mut [T]) -> Result<()> {
+        let n = self.n;
+        let h = n >> 1;
+        self.check(input.len(), output.len())?;
+        let x = |j: usize| input[j] * self.window[j];
+        for i in 0..h {
+            output[i] = -x(3 * h - 1 - i) - x(3 * h + i);
+            output[h + i] = x(i) - x(n - 1 - i);
+        }
+        self.plan.try_transform(output)
+    }
+
+    /// Perform the inverse transform and add the result to the output.
+    ///
+    /// The function reads `n` coefficients from `input` and adds `2 * n`
+    /// windowed points to `output`. The function panics if the data are not
+    /// compatible with the plan; see `Mdct::try_inverse`.
+    pub fn inverse(&self, input: &[T], output: &mut [T]) {
+        if let Err(error) = self.try_inverse(input, output) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Perform the inverse transform and add the result to the output if the
+    /// data are compatible with the plan.
+    ///
+    /// The function is the fallible counterpart of `Mdct::inverse`.
+    pub fn try_inverse(&self, input: &[T], output: &mut [T]) -> Result<()> {
+        let n = self.n;
+        let h = n >> 1;
+        self.check(output.len(), input.len())?;
+        let mut buffer = input.to_vec();
+        self.plan.try_transform(&mut buffer)?;
+        let factor = T::from(2.0 / n as f64).unwrap();
+        let mut y = |j: usize, value: T| output[j] = output[j] + value * factor * self.window[j];
+        for i in 0..h {
+            y(i, buffer[h + i]);
+            y(n - 1 - i, -buffer[h + i]);
+            y(3 * h - 1 - i, -buffer[i]);
+            y(3 * h + i, -buffer[i]);
+        }
+        Ok(())
+    }
+
+    fn check(&self, n: usize, m: usize) -> Result<()> {
+        if n != 2 * self.n {
+            return Err(Error::LengthMismatch {
+                plan: 2 * self.n,
+                data: n,
+            });
+        }
+        if m != self.n {
+            return Err(Error::LengthMismatch {
+                plan: self.n,
+                data: m,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn sine(n: usize) -> Vec<f64> {
+    let step = std::f64::consts::PI / (2 * n) as f64;
+    (0..(2 * n))
+        .map(|j| ((j as f64 + 0.5) * step).sin())
+        .collect()
+}
+
+fn kaiser_bessel(n: usize, alpha: f64) -> Vec<f64> {
+    let beta = std::f64::consts::PI * alpha;
+    let kaiser = (0..(n + 1))
+        .map(|j| {
+            let x = 2.0 * j as f64 / n as f64 - 1.0;
+            bessel(beta * (1.0 - x * x).sqrt())
+        })
+        .collect::<Vec<_>>();
+    let total = kaiser.iter().sum::<f64>();
+    let mut window = vec![0.0; 2 * n];
+    let mut sum = 0.0;
+    for j in 0..n {
+        sum += kaiser[j];
+        window[j] = (sum / total).sqrt();
+        window[2 * n - 1 - j] = window[j];
+    }
+    window
+}
+
+/// Compute the modified Bessel function of the first kind of order zero.
+fn bessel(x: f64) -> f64 {
+    let y = x * x / 4.0;
+    let (mut sum, mut term, mut k) = (1.0, 1.0, 1.0);
+    while term > 1e-17 * sum {
+        term *= y / (k * k);
+        sum += term;
+        k += 1.0;
+    }
+    sum
+}
@@ -0,0 +1,268 @@
+// The implementation is a decimation-in-time Cooley–Tukey algorithm with the
+// input permuted in mixed-radix digit-reversed order.
+
+use num_complex::Complex;
+use num_traits::Float;
+
+use {table, twiddle, Operation, Result};
+
+const SQRT_3_2: f64 = 0.866_025_403_784_438_6;
+
+const COS_2PI_5: f64 = 0.309_016_994_374_947_45;
+const COS_4PI_5: f64 = -0.809_016_994_374_947_5;
+const SIN_2PI_5: f64 = 0.951_056_516_295_153_5;
+const SIN_4PI_5: f64 = 0.587_785_252_292_473_2;
+
+const COS_2PI_7: f64 = 0.623_489_801_858_733_5;
+const COS_4PI_7: f64 = -0.222_520_933_956_314_4;
+const COS_6PI_7: f64 = -0.900_968_867_902_419_1;
+const SIN_2PI_7: f64 = 0.781_831_482_468_029_8;
+const SIN_4PI_7: f64 = 0.974_927_912_181_823_6;
+const SIN_6PI_7: f64 = 0.433_883_739_117_558_1;
+
+/// A mixed-radix transform for lengths with no prime factors other than 2, 3,
+/// 5, and 7.
+#[derive(Clone, Debug)]
+pub struct Mixed<T> {
+    radices: Vec<usize>,
+    factors: Vec<Complex<T>>,
+    swaps: Vec<(usize, usize)>,
+    sign: T,
+}
+
+impl<T> Mixed<T>
+where
+    T: Float,
+{
+    pub fn new(operation: Operation, radices: Vec<usize>) -> Result<Self> {
+        let one = T::one();
+        let sign = if let Operation::Forward = operation {
+            -one
+        } else {
+            one
+        };
+        let mut factors = table(radices.iter().product())?;
+        let mut span = 1;
+        for &radix in &radices {
+            for k in 0..span {
+                for j in 1..radix {
+                    factors.push(twiddle(j * k, span * radix, operation));
+                }
+            }
+            span *= radix;
+        }
+        Ok(Mixed {
+            swaps: permute(&radices),
+            radices,
+            factors,
+            sign,
+        })
+    }
+
+    pub fn transform(&self, data: &mut [Complex<T>]) {
+        for &(i, j) in &self.swaps {
+            data.swap(i, j);
+        }
+        let mut span = 1;
+        let mut offset = 0;
+        for &radix in &self.radices {
+            let factors = &self.factors[offset..(offset + span * (radix - 1))];
+            match radix {
+                2 => radix2(data, span, factors),
+                3 => radix3(data, span, factors, self.sign),
+                5 => radix5(data, span, factors, self.sign),
+                7 => radix7(data, span, factors, self.sign),
+                _ => unreachable!(),
+            }
+            offset += span * (radix - 1);
+            span *= radix;
+        }
+    }
+}
+
+/// Factor a number into the radices supported by the mixed-radix transform.
+///
+/// The function returns `None` if the number has a prime factor greater than 7.
+pub fn factorize(mut n: usize) -> Option<Vec<usize>> {
+    if n == 0 {
+        return None;
+    }
+    let mut radices = vec![];
+    for &radix in &[2, 3, 5, 7] {
+        while n.is_multiple_of(radix) {
+            radices.push(radix);
+            n /= radix;
+        }
+    }
+    if n == 1 {
+        Some(radices)
+    } else {
+        None
+    }
+}
+
+fn permute(radices: &[usize]) -> Vec<(usize, usize)> {
+    let mut order = vec![0];
+    for &radix in radices {
+        let m = order.len();
+        order = (0..(m * radix))
+            .map(|i| i / m + radix * order[i % m])
+            .collect();
+    }
+    let n = order.len();
+    let mut swaps = vec![];
+    let mut visited = vec![false; n];
+    for start in 0..n {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut i = start;
+        loop {
+            let j = order[i];
+            if j == start {
+                break;
+            }
+            visited[j] = true;
+            swaps.push((i, j));
+            i = j;
+        }
+    }
+    swaps
+}
+
+#[inline(always)]
+fn rotate<T>(value: Complex<T>, factor: T) -> Complex<T>
+where
+    T: Float,
+{
+    Complex::new(-value.im * factor, value.re * factor)
+}
+
+#[inline(always)]
+fn radix2<T>(data: &mut [Complex<T>], span: usize, factors: &[Complex<T>])
+where
+    T: Float,
+{
+    let n = data.len();
+    let mut base = 0;
+    while base < n {
+        for (k, &factor) in factors.iter().enumerate() {
+            let i = base + k;
+            let j = i + span;
+            let product = factor * data[j];
+            data[j] = data[i] - product;
+            data[i] = data[i] + product;
+        }
+        base += 2 * span;
+    }
+}
+
+#[inline(always)]
+fn radix3<T>(data: &mut [Complex<T>], span: usize, factors: &[Complex<T>], sign: T)
+where
+    T: Float,
+{
+    let n = data.len();
+    let half = T::from(0.5).unwrap();
+    let sine = sign * T::from(SQRT_3_2).unwrap();
+    let mut base = 0;
+    while base < n {
+        for k in 0..span {
+            let i0 = base + k;
+            let (i1, i2) = (i0 + span, i0 + 2 * span);
+            let x0 = data[i0];
+            let x1 = factors[2 * k] * data[i1];
+            let x2 = factors[2 * k + 1] * data[i2];
+            let sum = x1 + x2;
+            let common = x0 - sum.scale(half);
+            let difference = rotate(x1 - x2, sine);
+            data[i0] = x0 + sum;
+            data[i1] = common + difference;
+            data[i2] = common - difference;
+        }
+        base += 3 * span;
+    }
+}
+
+#[inline(always)]
+fn radix5<T>(data: &mut [Complex<T>], span: usize, factors: &[Complex<T>], sign: T)
+where
+    T: Float,
+{
+    let n = data.len();
+    let (c1, c2) = (T::from(COS_2PI_5).unwrap(), T::from(COS_4PI_5).unwrap());
+    let (s1, s2) = (
+        sign * T::from(SIN_2PI_5).unwrap(),
+        sign * T::from(SIN_4PI_5).unwrap(),
+    );
+    let mut base = 0;
+    while base < n {
+        for k in 0..span {
+            let i0 = base + k;
+            let (i1, i2, i3, i4) = (i0 + span, i0 + 2 * span, i0 + 3 * span, i0 + 4 * span);
+            let x0 = data[i0];
+            let x1 = factors[4 * k] * data[i1];
+            let x2 = factors[4 * k + 1] * data[i2];
+            let x3 = factors[4 * k + 2] * data[i3];
+            let x4 = factors[4 * k + 3] * data[i4];
+            let (a1, b1) = (x1 + x4, x1 - x4);
+            let (a2, b2) = (x2 + x3, x2 - x3);
+            let common1 = x0 + a1.scale(c1) + a2.scale(c2);
+            let common2 = x0 + a1.scale(c2) + a2.scale(c1);
+            let difference1 = rotate(b1, s1) + rotate(b2, s2);
+            let difference2 = rotate(b1, s2) - rotate(b2, s1);
+            data[i0] = x0 + a1 + a2;
+            data[i1] = common1 + difference1;
+            data[i2] = common2 + difference2;
+            data[i3] = common2 - difference2;
+            data[i4] = common1 - difference1;
+        }
+        base += 5 * span;
+    }
+}
+
+#[inline(always)]
+fn radix7<T>(data: &mut [Complex<T>], span: usize, factors: &[Complex<T>], sign: T)
+where
+    T: Float,
+{
+    let n = data.len();
+    let (c1, c2, c3) = (
+        T::from(COS_2PI_7).unwrap(),
+        T::from(COS_4PI_7).unwrap(),
+        T::from(COS_6PI_7).unwrap(),
+    );
+    let (s1, s2, s3) = (
+        sign * T::from(SIN_2PI_7).unwrap(),
+        sign * T::from(SIN_4PI_7).unwrap(),
+        sign * T::from(SIN_6PI_7).unwrap(),
+    );
+    let mut base = 0;
+    while base < n {
+        for k in 0..span {
+            let mut x = [Complex::new(T::zero(), T::zero()); 7];
+            x[0] = data[base + k];
+            for j in 1..7 {
+                x[j] = factors[6 * k + j - 1] * data[base + k + j * span];
+            }
+            let (a1, b1) = (x[1] + x[6], x[1] - x[6]);
+            let (a2, b2) = (x[2] + x[5], x[2] - x[5]);
+            let (a3, b3) = (x[3] + x[4], x[3] - x[4]);
+            let common1 = x[0] + a1.scale(c1) + a2.scale(c2) + a3.scale(c3);
+            let common2 = x[0] + a1.scale(c2) + a2.scale(c3) + a3.scale(c1);
+            let common3 = x[0] + a1.scale(c3) + a2.scale(c1) + a3.scale(c2);
+            let difference1 = rotate(b1, s1) + rotate(b2, s2) + rotate(b3, s3);
+            let difference2 = rotate(b1, s2) - rotate(b2, s3) - rotate(b3, s1);
+            let difference3 = rotate(b1, s3) - rotate(b2, s1) + rotate(b3, s2);
+            data[base + k] = x[0] + a1 + a2 + a3;
+            data[base + k + span] = common1 + difference1;
+            data[base + k + 2 * span] = common2 + difference2;
+            data[base + k + 3 * span] = common3 + difference3;
+            data[base + k + 4 * span] = common3 - difference3;
+            data[base + k + 5 * span] = common2 - difference2;
+            data[base + k + 6 * span] = common1 - difference1;
+        }
+        base += 7 * span;
+    }
+}
@@ -0,0 +1,372 @@
+//! Number-theoretic transforms.
+//!
+//! The module provides the [number-theoretic transform][1], which is the
+//! discrete Fourier transform over the integers modulo a prime `m`. The role of
+//! `exp(-2πi / n)` is played by an element of order `n` modulo `m`, which
+//! exists when `n` divides `m - 1`. Since the arithmetic is exact, the
+//! transform is suitable for multiplying large polynomials or integers without
+//! any round-off; results exceeding a single modulus can be obtained by
+//! transforming modulo several primes and combining the residues via
+//! `combine`.
+//!
+//! The transform is available for `u32` and `u64` and for powers of two, using
+//! the same butterflies as the complex transform. Common moduli include
+//! `998244353 = 119 * 2^23 + 1` and `2^64 - 2^32 + 1`, which allow for up to
+//! `2^23` and `2^32` points, respectively. The arithmetic is carried out using
+//! Montgomery’s reduction.
+//!
+//! ## Example
+//!
+//! ```
+//! use dft::ntt::Plan;
+//! use dft::Operation;
+//!
+//! let plan = Plan::new(Operation::Forward, 512, 998244353u32);
+//! let mut data = vec![42; 512];
+//! plan.transform(&mut data);
+//! ```
+//!
+//! [1]: https://en.wikipedia.org/wiki/Discrete_Fourier_transform_over_a_ring
+
+use std::fmt::Debug;
+
+use complex::rearrange;
+use {Error, Operation, Result};
+
+/// An integer type for which the transform is available.
+///
+/// The trait is implemented for `u32` and `u64` and cannot be implemented
+/// outside of the crate.
+pub trait Word: Copy + Debug + Eq + private::Sealed {
+    #[doc(hidden)]
+    const BITS: u32;
+
+    #[doc(hidden)]
+    fn from_u64(value: u64) -> Self;
+
+    #[doc(hidden)]
+    fn to_u64(self) -> u64;
+
+    #[doc(hidden)]
+    fn add(self, other: Self, modulus: Self) -> Self;
+
+    #[doc(hidden)]
+    fn subtract(self, other: Self, modulus: Self) -> Self;
+
+    /// Compute `self * other / 2^BITS` modulo the modulus given the inverse
+    /// of the modulus modulo `2^BITS`. The product should be smaller than
+    /// `modulus * 2^BITS`.
+    #[doc(hidden)]
+    fn reduce(self, other: Self, modulus: Self, inverse: Self) -> Self;
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for u32 {}
+    impl Sealed for u64 {}
+}
+
+macro_rules! word(
+    ($word:ty, $double:ty) => (
+        impl Word for $word {
+            const BITS: u32 = <$word>::BITS;
+
+            #[inline(always)]
+            fn from_u64(value: u64) -> Self {
+                value as $word
+            }
+
+            #[inline(always)]
+            fn to_u64(self) -> u64 {
+                self as u64
+            }
+
+            #[inline(always)]
+            fn add(self, other: Self, modulus: Self) -> Self {
+                let (sum, carry) = self.overflowing_add(other);
+                if carry || sum >= modulus {
+                    sum.wrapping_sub(modulus)
+                } else {
+                    sum
+                }
+            }
+
+            #[inline(always)]
+            fn subtract(self, other: Self, modulus: Self) -> Self {
+                if self >= other {
+                    self - other
+                } else {
+                    self.wrapping_sub(other).wrapping_add(modulus)
+                }
+            }
+
+            #[inline(always)]
+            fn reduce(self, other: Self, modulus: Self, inverse: Self) -> Self {
+                let product = self as $double * other as $double;
+                let factor = (product as $word).wrapping_mul(inverse);
+                let high = (product >> Self::BITS) as $word;
+                let correction = ((factor as $double * modulus as $double) >> Self::BITS) as $word;
+                if high >= correction {
+                    high - correction
+                } else {
+                    high.wrapping_sub(correction).wrapping_add(modulus)
+                }
+            }
+        }
+    );
+);
+
+word!(u32, u64);
+word!(u64, u128);
+
+/// A transform plan.
+#[derive(Clone, Debug)]
+pub struct Plan<W> {
+    n: usize,
+    modulus: W,
+    inverse: W,
+    square: W,
+    scale: W,
+    rotation: W,
+    factors: Vec<W>,
+}
+
+impl<W> Plan<W>
+where
+    W: Word,
+{
+    /// Create a plan for a specific operation, specific number of points, and
+    /// specific modulus.
+    ///
+    /// The function panics if the number of points or the modulus is not
+    /// supported; see `Plan::try_new`.
+    pub fn new(operation: Operation, n: usize, modulus: W) -> Self {
+        match Plan::try_new(operation, n, modulus) {
+            Ok(plan) => plan,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Create a plan for a specific operation, specific number of points, and
+    /// specific modulus if they are supported.
+    ///
+    /// The modulus should be an odd prime, which is verified, and the number
+    /// of points should be a power of two dividing the modulus minus one.
+    pub fn try_new(operation: Operation, n: usize, modulus: W) -> Result<Self> {
+        let m = modulus.to_u64() as u128;
+        if m < 3 || m & 1 == 0 || !prime(m) {
+            return Err(Error::UnsupportedModulus(m as u64));
+        }
+        if !n.is_power_of_two() || !(m - 1).is_multiple_of(n as u128) {
+            return Err(Error::UnsupportedLength(n));
+        }
+        let mut root = match (2..m.min(1 << 16))
+            .map(|value| power(value, (m - 1) / n as u128, m))
+            .find(|&root| n == 1 || power(root, n as u128 / 2, m) != 1)
+        {
+            Some(root) => root,
+            _ => return Err(Error::UnsupportedModulus(m as u64)),
+        };
+        if operation != Operation::Forward {
+            root = power(root, n as u128 - 1, m);
+        }
+        let radix = (1u128 << W::BITS) % m;
+        let montgomery = |value: u128| W::from_u64((value * radix % m) as u64);
+        let mut powers = Vec::with_capacity(n);
+        let mut value = 1;
+        for _ in 0..n {
+            powers.push(value);
+            value = value * root % m;
+        }
+        let mut factors = Vec::with_capacity(n);
+        let mut step = 1;
+        while step < n {
+            let jump = n / (2 * step);
+            factors.extend((0..step).map(|k| montgomery(powers[k * jump])));
+            step <<= 1;
+        }
+        let mut inverse = m as u64;
+        for _ in 0..6 {
+            inverse = inverse.wrapping_mul(2u64.wrapping_sub((m as u64).wrapping_mul(inverse)));
+        }
+        let scale = if operation == Operation::Inverse {
+            power(n as u128 % m, m - 2, m)
+        } else {
+            1
+        };
+        Ok(Plan {
+            n,
+            modulus,
+            inverse: W::from_u64(inverse),
+            square: W::from_u64((radix * radix % m) as u64),
+            scale: W::from_u64(scale as u64),
+            rotation: montgomery(powers[n / 4]),
+            factors,
+        })
+    }
+
+    /// Perform the transform.
+    ///
+    /// The function panics if the data are not compatible with the plan.
+    pub fn transform(&self, data: &mut [W]) {
+        if let Err(error) = self.try_transform(data) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Perform the transform if the data are compatible with the plan.
+    ///
+    /// The data can be any values of the type, but the result is always
+    /// reduced modulo the modulus.
+    pub fn try_transform(&self, data: &mut [W]) -> Result<()> {
+        let n = self.n;
+        if data.len() != n {
+            return Err(Error::LengthMismatch {
+                plan: n,
+                data: data.len(),
+            });
+        }
+        let (modulus, inverse) = (self.modulus, self.inverse);
+        for value in data.iter_mut() {
+            *value = value.reduce(self.square, modulus, inverse);
+        }
+        rearrange(data, n);
+        let mut step = 1;
+        if n.trailing_zeros() & 1 == 1 {
+            radix2(data, n, modulus);
+            step = 2;
+        }
+        while step < n {
+            radix4(data, n, step, self);
+            step <<= 2;
+        }
+        for value in data.iter_mut() {
+            *value = value.reduce(self.scale, modulus, inverse);
+        }
+        Ok(())
+    }
+
+    /// Multiply the data by other data element by element.
+    ///
+    /// The function is meant for multiplying transformed data before applying
+    /// the inverse transform. The data should be reduced modulo the modulus,
+    /// and the function panics if they are of different lengths.
+    pub fn multiply(&self, data: &mut [W], other: &[W]) {
+        assert!(data.len() == other.len());
+        let (modulus, inverse) = (self.modulus, self.inverse);
+        for (value, &other) in data.iter_mut().zip(other) {
+            *value = value
+                .reduce(other, modulus, inverse)
+                .reduce(self.square, modulus, inverse);
+        }
+    }
+}
+
+/// Combine residues modulo pairwise distinct primes into the residue modulo
+/// their product.
+///
+/// The function uses Garner’s algorithm. The product of the moduli should fit
+/// in `u128`, and the function panics if the slices are of different lengths.
+pub fn combine(residues: &[u64], moduli: &[u64]) -> u128 {
+    assert!(residues.len() == moduli.len());
+    let (mut result, mut product) = (0u128, 1u128);
+    for (&residue, &modulus) in residues.iter().zip(moduli) {
+        let m = modulus as u128;
+        let difference = (residue as u128 % m + m - result % m) % m;
+        let factor = difference * power(product % m, m - 2, m) % m;
+        result += product * factor;
+        product *= m;
+    }
+    result
+}
+
+/// Perform the first stage with radix-2 butterflies, which need no factors.
+#[inline(always)]
+fn radix2<W>(data: &mut [W], n: usize, modulus: W)
+where
+    W: Word,
+{
+    let mut i = 0;
+    while i < n {
+        let (x0, x1) = (data[i], data[i + 1]);
+        data[i] = x0.add(x1, modulus);
+        data[i + 1] = x0.subtract(x1, modulus);
+        i += 2;
+    }
+}
+
+/// Perform the two stages starting from a specific step with radix-4
+/// butterflies.
+#[inline(always)]
+fn radix4<W>(data: &mut [W], n: usize, step: usize, plan: &Plan<W>)
+where
+    W: Word,
+{
+    let (modulus, inverse) = (plan.modulus, plan.inverse);
+    let multiply = |x: W, y: W| x.reduce(y, modulus, inverse);
+    let jump = step << 2;
+    for k in 0..step {
+        let factor1 = plan.factors[step - 1 + k];
+        let factor2 = plan.factors[2 * step - 1 + k];
+        let factor3 = multiply(factor1, factor2);
+        let mut i = k;
+        while i < n {
+            let x0 = data[i];
+            let x1 = multiply(factor1, data[i + step]);
+            let x2 = multiply(factor2, data[i + 2 * step]);
+            let x3 = multiply(factor3, data[i + 3 * step]);
+            let (sum1, difference1) = (x0.add(x1, modulus), x0.subtract(x1, modulus));
+            let sum2 = x2.add(x3, modulus);
+            let difference2 = multiply(x2.subtract(x3, modulus), plan.rotation);
+            data[i] = sum1.add(sum2, modulus);
+            data[i + step] = difference1.add(difference2, modulus);
+            data[i + 2 * step] = sum1.subtract(sum2, modulus);
+            data[i + 3 * step] = difference1.subtract(difference2, modulus);
+            i += jump;
+        }
+    }
+}
+
+/// Check if a number is prime using the deterministic variant of the
+/// Miller–Rabin test, which is exact for all numbers below `2^64`.
+fn prime(n: u128) -> bool {
+    const BASES: [u128; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    for &base in &BASES {
+        if n.is_multiple_of(base) {
+            return n == base;
+        }
+    }
+    let shift = (n - 1).trailing_zeros();
+    let odd = (n - 1) >> shift;
+    'outer: for &base in &BASES {
+        let mut value = power(base, odd, n);
+        if value == 1 || value == n - 1 {
+            continue;
+        }
+        for _ in 1..shift {
+            value = value * value % n;
+            if value == n - 1 {
+                continue 'outer;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+fn power(mut base: u128, mut exponent: u128, modulus: u128) -> u128 {
+    let mut result = 1;
+    base %= modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result
+}
@@ -0,0 +1,102 @@
+// The implementation distributes the split-radix steps of large transforms for
+// powers of two across scoped threads. The three parts of each step are
+// transformed concurrently, and the step itself is split into chunks. The
+// calling thread takes a share of the work so that no more threads than
+// requested run at once. Each element undergoes the same arithmetic as with a
+// single thread.
+
+use num_complex::Complex;
+use num_traits::Float;
+use std::thread;
+
+use complex::{butterflies, combine, quarters};
+use {is, Operation, Plan, Simd};
+
+const PARALLEL: usize = 1 << 14;
+
+/// Perform the butterflies on bit-reversed data using the threads of the plan
+/// if the type and the size of the data allow for it.
+pub fn split<T>(data: &mut [Complex<T>], plan: &Plan<T>) -> bool
+where
+    T: Float + 'static,
+{
+    let threads = plan.options.threads;
+    if threads < 2 || data.len() < PARALLEL {
+        return false;
+    }
+    let (operation, simd) = (plan.operation, plan.options.simd);
+    unsafe {
+        if is::<T, f32>() {
+            let data = &mut *(data as *mut [Complex<T>] as *mut [Complex<f32>]);
+            let factors = &*(&plan.factors[..] as *const [Complex<T>] as *const [Complex<f32>]);
+            run(data, factors, operation, simd, threads);
+        } else if is::<T, f64>() {
+            let data = &mut *(data as *mut [Complex<T>] as *mut [Complex<f64>]);
+            let factors = &*(&plan.factors[..] as *const [Complex<T>] as *const [Complex<f64>]);
+            run(data, factors, operation, simd, threads);
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+fn run<T>(
+    data: &mut [Complex<T>],
+    factors: &[Complex<T>],
+    operation: Operation,
+    simd: Simd,
+    threads: usize,
+) where
+    T: Float + Send + Sync + 'static,
+{
+    let n = data.len();
+    if threads < 2 || n < PARALLEL {
+        butterflies(data, factors, operation, simd);
+        return;
+    }
+    let (h, q) = (n >> 1, n >> 2);
+    {
+        let (first, rest) = data.split_at_mut(h);
+        let (second, third) = rest.split_at_mut(q);
+        thread::scope(|scope| {
+            scope.spawn(|| run(first, factors, operation, simd, threads / 2));
+            if threads < 3 {
+                run(second, factors, operation, simd, 1);
+            } else {
+                scope.spawn(|| run(second, factors, operation, simd, threads / 4));
+            }
+            run(third, factors, operation, simd, threads / 4);
+        });
+    }
+    // The chunks are kept a multiple of the widest vector.
+    let chunk = (q.div_ceil(threads) + 3) & !3;
+    let [x0, x1, z1, z3] = quarters(data);
+    let factors1 = factors[(h - 1)..(h - 1 + q)].chunks(chunk);
+    let factors2 = factors[(q - 1)..(2 * q - 1)].chunks(chunk);
+    let step = move |((((x0, x1), z1), z3), (factors1, factors2))| {
+        combine(
+            &mut [x0, x1, z1, z3],
+            &[factors1, factors2],
+            operation,
+            simd,
+        )
+    };
+    thread::scope(|scope| {
+        let mut parts = x0
+            .chunks_mut(chunk)
+            .zip(x1.chunks_mut(chunk))
+            .zip(z1.chunks_mut(chunk))
+            .zip(z3.chunks_mut(chunk))
+            .zip(factors1.zip(factors2));
+        // There are at most as many chunks as threads, and the calling thread
+        // takes the first one.
+        let own = parts.next();
+        for part in parts {
+            scope.spawn(move || step(part));
+        }
+        if let Some(part) = own {
+            step(part);
+        }
+    });
+}
@@ -0,0 +1,237 @@
+use num_complex::Complex;
+use num_traits::{Float, FloatConst};
+
+use real::{try_c2r, try_r2c};
+use {Error, Operation, Options, Plan, Result};
+
+pub const BLOCK: usize = 16;
+
+/// A two-dimensional transform plan.
+///
+/// The plan transforms data stored in row-major order. The rows are
+/// transformed first and the columns second, using one-dimensional plans of
+/// the corresponding lengths. The columns are transformed in blocks, which
+/// are gathered into a buffer so that each column becomes contiguous.
+///
+/// The plan can also be applied to real data via `Plan2::r2c` and
+/// `Plan2::c2r`, in which case only the `rows * (columns / 2 + 1)`
+/// non-redundant components of the spectrum are stored; see `unpack2`.
+#[derive(Clone, Debug)]
+pub struct Plan2<T> {
+    rows: usize,
+    columns: usize,
+    row: Plan<T>,
+    column: Plan<T>,
+}
+
+impl<T> Plan2<T>
+where
+    T: Float + FloatConst + 'static,
+{
+    /// Create a plan for a specific operation and specific numbers of rows
+    /// and columns.
+    ///
+    /// The function panics if the numbers are not supported; see
+    /// `Plan2::try_new`.
+    #[inline]
+    pub fn new(operation: Operation, rows: usize, columns: usize) -> Self {
+        Plan2::with_options(operation, rows, columns, Options::default())
+    }
+
+    /// Create a plan for a specific operation and specific numbers of rows
+    /// and columns if the numbers are supported.
+    #[inline]
+    pub fn try_new(operation: Operation, rows: usize, columns: usize) -> Result<Self> {
+        Plan2::try_with_options(operation, rows, columns, Options::default())
+    }
+
+    /// Create a plan for a specific operation, specific numbers of rows and
+    /// columns, and specific options.
+    ///
+    /// The function panics if the numbers are not supported; see
+    /// `Plan2::try_with_options`.
+    pub fn with_options(
+        operation: Operation,
+        rows: usize,
+        columns: usize,
+        options: Options,
+    ) -> Self {
+        match Plan2::try_with_options(operation, rows, columns, options) {
+            Ok(plan) => plan,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Create a plan for a specific operation, specific numbers of rows and
+    /// columns, and specific options if the numbers are supported.
+    pub fn try_with_options(
+        operation: Operation,
+        rows: usize,
+        columns: usize,
+        options: Options,
+    ) -> Result<Self> {
+        Ok(Plan2 {
+            rows,
+            columns,
+            row: Plan::try_with_options(operation, columns, options)?,
+            column: Plan::try_with_options(operation, rows, options)?,
+        })
+    }
+
+    /// Perform the transform.
+    ///
+    /// The function panics if the data are not compatible with the plan.
+    pub fn transform(&self, data: &mut [Complex<T>]) {
+        if let Err(error) = self.try_transform(data) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Perform the transform if the data are compatible with the plan.
+    pub fn try_transform(&self, data: &mut [Complex<T>]) -> Result<()> {
+        let (rows, columns) = (self.rows, self.columns);
+        if data.len() != rows * columns {
+            return Err(Error::LengthMismatch {
+                plan: rows * columns,
+                data: data.len(),
+            });
+        }
+        self.row.try_transform_batch(data, rows, columns)?;
+        let mut buffer = vec![Complex::new(T::zero(), T::zero()); rows * BLOCK.min(columns)];
+        transform_columns(data, &self.column, columns, &mut buffer)
+    }
+
+    /// Perform the forward transform of real data out of place.
+    ///
+    /// The function writes the `rows * (columns / 2 + 1)` non-redundant
+    /// components of the complex transform of `input` to `output` in
+    /// row-major order. The plan should be for the forward operation.
+    ///
+    /// The function panics if the data are not compatible with the plan; see
+    /// `Plan2::try_r2c`.
+    pub fn r2c(&self, input: &[T], output: &mut [Complex<T>]) {
+        if let Err(error) = self.try_r2c(input, output) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Perform the forward transform of real data out of place if the data
+    /// are compatible with the plan.
+    ///
+    /// The function is the fallible counterpart of `Plan2::r2c`.
+    pub fn try_r2c(&self, input: &[T], output: &mut [Complex<T>]) -> Result<()> {
+        let (rows, columns) = (self.rows, self.columns);
+        let width = (columns >> 1) + 1;
+        self.check(input.len(), output.len())?;
+        for (input, output) in input.chunks(columns).zip(output.chunks_mut(width)) {
+            try_r2c(input, output, &self.row)?;
+        }
+        let mut buffer = vec![Complex::new(T::zero(), T::zero()); rows * BLOCK.min(width)];
+        transform_columns(output, &self.column, width, &mut buffer)
+    }
+
+    /// Perform the backward or inverse transform of real data out of place.
+    ///
+    /// The function reads the `rows * (columns / 2 + 1)` non-redundant
+    /// components of a complex transform from `input` and writes the
+    /// corresponding real data to `output`. The plan should be for the
+    /// backward or inverse operation.
+    ///
+    /// The function panics if the data are not compatible with the plan; see
+    /// `Plan2::try_c2r`.
+    pub fn c2r(&self, input: &[Complex<T>], output: &mut [T]) {
+        if let Err(error) = self.try_c2r(input, output) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Perform the backward or inverse transform of real data out of place if
+    /// the data are compatible with the plan.
+    ///
+    /// The function is the fallible counterpart of `Plan2::c2r`.
+    pub fn try_c2r(&self, input: &[Complex<T>], output: &mut [T]) -> Result<()> {
+        let (rows, columns) = (self.rows, self.columns);
+        let width = (columns >> 1) + 1;
+        self.check(output.len(), input.len())?;
+        if self.row.operation == Operation::Forward {
+            return Err(Error::OperationMismatch(self.row.operation));
+        }
+        let mut data = input.to_vec();
+        let mut buffer = vec![Complex::new(T::zero(), T::zero()); rows * BLOCK.min(width)];
+        transform_columns(&mut data, &self.column, width, &mut buffer)?;
+        for (input, output) in data.chunks(width).zip(output.chunks_mut(columns)) {
+            try_c2r(input, output, &self.row)?;
+        }
+        Ok(())
+    }
+
+    fn check(&self, n: usize, m: usize) -> Result<()> {
+        let (rows, columns) = (self.rows, self.columns);
+        if n != rows * columns {
+            return Err(Error::LengthMismatch {
+                plan: rows * columns,
+                data: n,
+            });
+        }
+        if m != rows * ((columns >> 1) + 1) {
+            return Err(Error::LengthMismatch {
+                plan: rows * ((columns >> 1) + 1),
+                data: m,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Unpack the result produced by the forward transform applied to real
+/// two-dimensional data.
+///
+/// The function expands the `rows * (columns / 2 + 1)` non-redundant
+/// components written by `Plan2::r2c` to the full `rows * columns` spectrum
+/// using its Hermitian symmetry.
+pub fn unpack2<T>(data: &[Complex<T>], rows: usize, columns: usize) -> Vec<Complex<T>>
+where
+    T: Float,
+{
+    let width = (columns >> 1) + 1;
+    assert!(data.len() == rows * width);
+    let mut result = vec![Complex::new(T::zero(), T::zero()); rows * columns];
+    for (i, row) in result.chunks_mut(columns).enumerate() {
+        row[..width].copy_from_slice(&data[(i * width)..((i + 1) * width)]);
+        let k = (rows - i) % rows;
+        for j in width..columns {
+            row[j] = data[k * width + columns - j].conj();
+        }
+    }
+    result
+}
+
+/// Transform the columns of data stored in row-major order.
+///
+/// The buffer should be able to hold `BLOCK.min(columns)` columns.
+pub fn transform_columns<T>(
+    data: &mut [Complex<T>],
+    plan: &Plan<T>,
+    columns: usize,
+    buffer: &mut [Complex<T>],
+) -> Result<()>
+where
+    T: Float + 'static,
+{
+    let rows = plan.n;
+    for start in (0..columns).step_by(BLOCK) {
+        let width = BLOCK.min(columns - start);
+        for (i, row) in data.chunks(columns).enumerate() {
+            for (j, &value) in row[start..(start + width)].iter().enumerate() {
+                buffer[j * rows + i] = value;
+            }
+        }
+        plan.try_transform_batch(buffer, width, rows)?;
+        for (i, row) in data.chunks_mut(columns).enumerate() {
+            for (j, value) in row[start..(start + width)].iter_mut().enumerate() {
+                *value = buffer[j * rows + i];
+            }
+        }
+    }
+    Ok(())
+}
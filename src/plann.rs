@@ -0,0 +1,133 @@
+use num_complex::Complex;
+use num_traits::{Float, FloatConst};
+
+use plan2::{transform_columns, BLOCK};
+use {Error, Operation, Options, Plan, Result};
+
+/// A multidimensional transform plan.
+///
+/// The plan transforms data stored in row-major order along a subset of the
+/// axes of a given shape, one axis after another. The axes of equal length
+/// share the same one-dimensional plan.
+#[derive(Clone, Debug)]
+pub struct PlanN<T> {
+    shape: Vec<usize>,
+    axes: Vec<(usize, usize)>,
+    plans: Vec<Plan<T>>,
+}
+
+impl<T> PlanN<T>
+where
+    T: Float + FloatConst + 'static,
+{
+    /// Create a plan for a specific operation, a specific shape, and specific
+    /// axes.
+    ///
+    /// The function panics if the shape or the axes are not supported; see
+    /// `PlanN::try_new`.
+    #[inline]
+    pub fn new(operation: Operation, shape: &[usize], axes: &[usize]) -> Self {
+        PlanN::with_options(operation, shape, axes, Options::default())
+    }
+
+    /// Create a plan for a specific operation, a specific shape, and specific
+    /// axes if the shape and the axes are supported.
+    #[inline]
+    pub fn try_new(operation: Operation, shape: &[usize], axes: &[usize]) -> Result<Self> {
+        PlanN::try_with_options(operation, shape, axes, Options::default())
+    }
+
+    /// Create a plan for a specific operation, a specific shape, specific
+    /// axes, and specific options.
+    ///
+    /// The function panics if the shape or the axes are not supported; see
+    /// `PlanN::try_with_options`.
+    pub fn with_options(
+        operation: Operation,
+        shape: &[usize],
+        axes: &[usize],
+        options: Options,
+    ) -> Self {
+        match PlanN::try_with_options(operation, shape, axes, options) {
+            Ok(plan) => plan,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Create a plan for a specific operation, a specific shape, specific
+    /// axes, and specific options if the shape and the axes are supported.
+    pub fn try_with_options(
+        operation: Operation,
+        shape: &[usize],
+        axes: &[usize],
+        options: Options,
+    ) -> Result<Self> {
+        let mut plans: Vec<Plan<T>> = vec![];
+        let mut pairs = Vec::with_capacity(axes.len());
+        for &axis in axes {
+            if axis >= shape.len() {
+                return Err(Error::UnsupportedAxis {
+                    axis,
+                    dimensions: shape.len(),
+                });
+            }
+            let n = shape[axis];
+            let index = match plans.iter().position(|plan| plan.n == n) {
+                Some(index) => index,
+                None => {
+                    plans.push(Plan::try_with_options(operation, n, options)?);
+                    plans.len() - 1
+                }
+            };
+            pairs.push((axis, index));
+        }
+        Ok(PlanN {
+            shape: shape.to_vec(),
+            axes: pairs,
+            plans,
+        })
+    }
+}
+
+impl<T> PlanN<T>
+where
+    T: Float + 'static,
+{
+    /// Perform the transform.
+    ///
+    /// The function panics if the data are not compatible with the plan.
+    pub fn transform(&self, data: &mut [Complex<T>]) {
+        if let Err(error) = self.try_transform(data) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Perform the transform if the data are compatible with the plan.
+    pub fn try_transform(&self, data: &mut [Complex<T>]) -> Result<()> {
+        let size = self.shape.iter().product();
+        if data.len() != size {
+            return Err(Error::LengthMismatch {
+                plan: size,
+                data: data.len(),
+            });
+        }
+        if size == 0 {
+            return Ok(());
+        }
+        let mut buffer = vec![];
+        for &(axis, index) in &self.axes {
+            let plan = &self.plans[index];
+            let n = self.shape[axis];
+            let stride: usize = self.shape[(axis + 1)..].iter().product();
+            if stride == 1 {
+                plan.try_transform_batch(data, size / n, n)?;
+                continue;
+            }
+            buffer.resize(n * BLOCK.min(stride), Complex::new(T::zero(), T::zero()));
+            for block in data.chunks_mut(n * stride) {
+                transform_columns(block, plan, stride, &mut buffer)?;
+            }
+        }
+        Ok(())
+    }
+}
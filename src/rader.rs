@@ -0,0 +1,132 @@
+// The implementation expresses the transform of a prime length as a cyclic
+// convolution of the length one less, which is computed using a transform of
+// that length: https://en.wikipedia.org/wiki/Rader%27s_FFT_algorithm
+
+use num_complex::Complex;
+use num_traits::{Float, FloatConst};
+
+use complex::{compute, compute_with};
+use {table, twiddle, Operation, Options, Plan, Result};
+
+/// A transform for prime lengths.
+#[derive(Clone, Debug)]
+pub struct Rader<T> {
+    input: Vec<usize>,
+    output: Vec<usize>,
+    spectrum: Vec<Complex<T>>,
+    plan: Plan<T>,
+}
+
+impl<T> Rader<T>
+where
+    T: Float + FloatConst + 'static,
+{
+    pub fn new(operation: Operation, n: usize, options: Options) -> Result<Self> {
+        let m = n - 1;
+        let generator = generator(n);
+        let inverse = power(generator, m - 1, n);
+        let mut input = table(m)?;
+        let mut output = table(m)?;
+        let (mut i, mut j) = (1, 1);
+        for _ in 0..m {
+            input.push(i);
+            output.push(j);
+            i = multiply(i, inverse, n);
+            j = multiply(j, generator, n);
+        }
+        let factor = T::from(m).unwrap().recip();
+        let plan = Plan::complex(Operation::Forward, m, options)?;
+        let mut spectrum = table(m)?;
+        spectrum.extend(
+            output
+                .iter()
+                .map(|&k| twiddle::<T>(k, n, operation).scale(factor)),
+        );
+        compute(&mut spectrum, &plan);
+        Ok(Rader {
+            input,
+            output,
+            spectrum,
+            plan,
+        })
+    }
+}
+
+impl<T> Rader<T> {
+    pub fn scratch(&self) -> usize {
+        self.plan.n + self.plan.scratch()
+    }
+}
+
+impl<T> Rader<T>
+where
+    T: Float + 'static,
+{
+    pub fn transform(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        let (buffer, scratch) = scratch.split_at_mut(self.plan.n);
+        for (value, &i) in buffer.iter_mut().zip(&self.input) {
+            *value = data[i];
+        }
+        compute_with(buffer, scratch, &self.plan);
+        let sum = buffer[0];
+        for (value, &spectrum) in buffer.iter_mut().zip(&self.spectrum) {
+            *value = (*value * spectrum).conj();
+        }
+        compute_with(buffer, scratch, &self.plan);
+        let first = data[0];
+        data[0] = first + sum;
+        for (&i, &value) in self.output.iter().zip(buffer.iter()) {
+            data[i] = first + value.conj();
+        }
+    }
+}
+
+/// Check if a number is a prime suitable for Rader’s algorithm.
+///
+/// The function requires the number minus one to have no prime factors other
+/// than 2, 3, 5, and 7 so that the convolution can be computed efficiently.
+pub fn suitable(n: usize) -> bool {
+    if n < 3 || ::mixed::factorize(n - 1).is_none() {
+        return false;
+    }
+    let mut i = 2;
+    while i * i <= n {
+        if n.is_multiple_of(i) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+fn generator(n: usize) -> usize {
+    let m = n - 1;
+    let factors = [2, 3, 5, 7]
+        .iter()
+        .cloned()
+        .filter(|&factor| m.is_multiple_of(factor))
+        .collect::<Vec<_>>();
+    (2..n)
+        .find(|&candidate| {
+            factors
+                .iter()
+                .all(|&factor| power(candidate, m / factor, n) != 1)
+        })
+        .unwrap()
+}
+
+fn multiply(a: usize, b: usize, n: usize) -> usize {
+    ((a as u128 * b as u128) % n as u128) as usize
+}
+
+fn power(mut base: usize, mut exponent: usize, n: usize) -> usize {
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply(result, base, n);
+        }
+        base = multiply(base, base, n);
+        exponent >>= 1;
+    }
+    result
+}
@@ -1,41 +1,218 @@
 use num_complex::Complex;
-use num_traits::Float;
+use num_traits::{Float, FloatConst};
 use std::slice::from_raw_parts_mut;
 
-use {Operation, Plan, Transform};
+use complex::{compute, compute_with, reserve};
+use {Error, Operation, Plan, Result, Transform};
 
 impl<T> Transform<T> for [T]
 where
-    T: Float,
+    T: Float + FloatConst + 'static,
 {
-    fn transform(&mut self, plan: &Plan<T>) {
+    fn try_transform(&mut self, plan: &Plan<T>) -> Result<()> {
+        self.try_transform_with_buffer(plan, &mut Vec::new())
+    }
+
+    fn try_transform_with_buffer(
+        &mut self,
+        plan: &Plan<T>,
+        buffer: &mut Vec<Complex<T>>,
+    ) -> Result<()> {
         let n = self.len();
-        assert!(n == plan.n);
-        let h = n >> 1;
-        if h == 0 {
-            return;
+        if n != plan.n {
+            return Err(Error::LengthMismatch {
+                plan: plan.n,
+                data: n,
+            });
         }
-        let data = unsafe { from_raw_parts_mut(self.as_mut_ptr() as *mut _, h) };
-        match plan.operation {
-            Operation::Forward => {
-                data.transform(plan);
-                compose(data, h, &plan.factors, false);
+        if n & 1 == 1 {
+            if n > 1 {
+                let scratch = reserve(buffer, n + plan.scratch());
+                transform_odd(self, plan, scratch);
             }
-            Operation::Backward | Operation::Inverse => {
-                compose(data, h, &plan.factors, true);
-                data.transform(plan);
+        } else {
+            let data = unsafe { from_raw_parts_mut(self.as_mut_ptr() as *mut _, n >> 1) };
+            match plan.operation {
+                Operation::Forward => forward(data, plan, buffer)?,
+                Operation::Backward | Operation::Inverse => backward(data, plan, buffer)?,
             }
         }
+        if let Some(factor) = plan.factor(n) {
+            scale(self, factor);
+        }
+        Ok(())
     }
 }
 
 impl<T> Transform<T> for Vec<T>
 where
-    T: Float,
+    T: Float + FloatConst + 'static,
 {
     #[inline(always)]
-    fn transform(&mut self, plan: &Plan<T>) {
-        Transform::transform(&mut self[..], plan)
+    fn try_transform(&mut self, plan: &Plan<T>) -> Result<()> {
+        Transform::try_transform(&mut self[..], plan)
+    }
+}
+
+/// Perform the forward transform of real data out of place.
+///
+/// The function writes the `n / 2 + 1` non-redundant components of the complex
+/// transform of `input` to `output`. The plan should be for the forward
+/// operation. No memory is allocated unless the number of points is odd, in
+/// which case the data are transformed in a temporary complex buffer, or the
+/// plan uses the Stockham kernel, which allocates its scratch buffer, or
+/// resorts to Bailey’s, Bluestein’s, or Rader’s algorithm. In addition, when
+/// the number of points is even but not a power of two, the first transform of
+/// real data with the plan builds the plan for half the number of points
+/// unless the plan has been created with the `real` option.
+///
+/// The function panics if the data are not compatible with the plan; see
+/// `try_r2c`.
+pub fn r2c<T>(input: &[T], output: &mut [Complex<T>], plan: &Plan<T>)
+where
+    T: Float + FloatConst + 'static,
+{
+    if let Err(error) = try_r2c(input, output, plan) {
+        panic!("{}", error);
+    }
+}
+
+/// Perform the forward transform of real data out of place if the data are
+/// compatible with the plan.
+///
+/// The function is the fallible counterpart of `r2c`.
+pub fn try_r2c<T>(input: &[T], output: &mut [Complex<T>], plan: &Plan<T>) -> Result<()>
+where
+    T: Float + FloatConst + 'static,
+{
+    let n = input.len();
+    let h = n >> 1;
+    check(n, output.len(), plan)?;
+    if plan.operation != Operation::Forward {
+        return Err(Error::OperationMismatch(plan.operation));
+    }
+    if n & 1 == 1 {
+        let mut buffer = input
+            .iter()
+            .map(|&value| value.into())
+            .collect::<Vec<Complex<T>>>();
+        compute(&mut buffer, plan);
+        output.copy_from_slice(&buffer[..(h + 1)]);
+    } else {
+        for (value, pair) in output.iter_mut().zip(input.chunks(2)) {
+            *value = Complex::new(pair[0], pair[1]);
+        }
+        forward(&mut output[..h], plan, &mut vec![])?;
+        let first = output[0];
+        output[0] = first.re.into();
+        output[h] = first.im.into();
+    }
+    if let Some(factor) = plan.factor(n) {
+        for value in output {
+            *value = value.scale(factor);
+        }
+    }
+    Ok(())
+}
+
+/// Perform the backward or inverse transform of real data out of place.
+///
+/// The function reads the `n / 2 + 1` non-redundant components of a complex
+/// transform from `input` and writes the corresponding real data to `output`.
+/// The plan should be for the backward or inverse operation. Memory is
+/// allocated in the same cases as for `r2c`.
+///
+/// The function panics if the data are not compatible with the plan; see
+/// `try_c2r`.
+pub fn c2r<T>(input: &[Complex<T>], output: &mut [T], plan: &Plan<T>)
+where
+    T: Float + FloatConst + 'static,
+{
+    if let Err(error) = try_c2r(input, output, plan) {
+        panic!("{}", error);
+    }
+}
+
+/// Perform the backward or inverse transform of real data out of place if the
+/// data are compatible with the plan.
+///
+/// The function is the fallible counterpart of `c2r`.
+pub fn try_c2r<T>(input: &[Complex<T>], output: &mut [T], plan: &Plan<T>) -> Result<()>
+where
+    T: Float + FloatConst + 'static,
+{
+    let n = output.len();
+    let h = n >> 1;
+    check(n, input.len(), plan)?;
+    if plan.operation == Operation::Forward {
+        return Err(Error::OperationMismatch(plan.operation));
+    }
+    if n & 1 == 1 {
+        let mut buffer = vec![Complex::new(T::zero(), T::zero()); n];
+        buffer[..(h + 1)].copy_from_slice(input);
+        for i in (h + 1)..n {
+            buffer[i] = buffer[n - i].conj();
+        }
+        compute(&mut buffer, plan);
+        for (value, &complex) in output.iter_mut().zip(&buffer) {
+            *value = complex.re;
+        }
+    } else {
+        let data = unsafe { from_raw_parts_mut(output.as_mut_ptr() as *mut _, h) };
+        data.copy_from_slice(&input[..h]);
+        data[0] = Complex::new(input[0].re, input[h].re);
+        backward(data, plan, &mut vec![])?;
+    }
+    if let Some(factor) = plan.factor(n) {
+        scale(output, factor);
+    }
+    Ok(())
+}
+
+/// Pack a spectrum into the layout produced by the forward transform applied to
+/// real data.
+///
+/// The function is the inverse of `unpack`. Only the first `n / 2 + 1`
+/// components of the spectrum are read, and the imaginary parts of the
+/// components that are real for real data are ignored. The result can be
+/// passed to `Transform::transform` with `Operation::Backward` or
+/// `Operation::Inverse`.
+pub fn pack<T>(data: &[Complex<T>]) -> Vec<T>
+where
+    T: Float,
+{
+    let mut result = vec![T::zero(); data.len()];
+    pack_into(data, &mut result);
+    result
+}
+
+/// Pack a spectrum into the layout produced by the forward transform applied to
+/// real data without allocating.
+///
+/// The function is the same as `pack` except that it writes the result to
+/// `result`, which should be of the same length as `data`.
+pub fn pack_into<T>(data: &[Complex<T>], result: &mut [T])
+where
+    T: Float,
+{
+    let n = data.len();
+    assert!(result.len() == n);
+    let h = n >> 1;
+    result[0] = data[0].re;
+    if h == 0 {
+        return;
+    }
+    if n & 1 == 1 {
+        for i in 1..(h + 1) {
+            result[2 * i - 1] = data[i].re;
+            result[2 * i] = data[i].im;
+        }
+    } else {
+        for i in 1..h {
+            result[2 * i] = data[i].re;
+            result[2 * i + 1] = data[i].im;
+        }
+        result[1] = data[h].re;
     }
 }
 
@@ -45,26 +222,130 @@ where
 /// with `Operation::Forward` to real data. See the top-level description of the
 /// crate for further details.
 pub fn unpack<T>(data: &[T]) -> Vec<Complex<T>>
+where
+    T: Float,
+{
+    let mut result = vec![Complex::new(T::zero(), T::zero()); data.len()];
+    unpack_into(data, &mut result);
+    result
+}
+
+/// Unpack the result produced by the forward transform applied to real data
+/// without allocating.
+///
+/// The function is the same as `unpack` except that it writes the result to
+/// `result`, which should be of the same length as `data`.
+pub fn unpack_into<T>(data: &[T], result: &mut [Complex<T>])
 where
     T: Float,
 {
     let n = data.len();
-    assert!(n.is_power_of_two());
+    assert!(result.len() == n);
     let h = n >> 1;
-    let mut result = Vec::with_capacity(n);
-    unsafe { result.set_len(n) };
+    unpack_half_into(data, &mut result[..(h + 1)]);
+    for i in (h + 1)..n {
+        result[i] = result[n - i].conj();
+    }
+}
+
+/// Unpack the non-redundant half of the result produced by the forward
+/// transform applied to real data without allocating.
+///
+/// The function is the same as `unpack_into` except that it writes only the
+/// first `n / 2 + 1` components, which is the length `result` should have.
+pub fn unpack_half_into<T>(data: &[T], result: &mut [Complex<T>])
+where
+    T: Float,
+{
+    let n = data.len();
+    let h = n >> 1;
+    assert!(result.len() == h + 1);
     result[0] = data[0].into();
     if h == 0 {
-        return result;
+        return;
     }
-    for i in 1..h {
-        result[i] = Complex::new(data[2 * i], data[2 * i + 1]);
+    if n & 1 == 1 {
+        for i in 1..(h + 1) {
+            result[i] = Complex::new(data[2 * i - 1], data[2 * i]);
+        }
+    } else {
+        for i in 1..h {
+            result[i] = Complex::new(data[2 * i], data[2 * i + 1]);
+        }
+        result[h] = data[1].into();
     }
-    result[h] = data[1].into();
-    for i in (h + 1)..n {
-        result[i] = result[n - i].conj();
+}
+
+/// Perform the transform of real data of an odd length using a scratch buffer
+/// of the length of the data plus the length given by `Plan::scratch`.
+fn transform_odd<T>(data: &mut [T], plan: &Plan<T>, scratch: &mut [Complex<T>])
+where
+    T: Float + 'static,
+{
+    let n = data.len();
+    let h = n >> 1;
+    let (buffer, scratch) = scratch.split_at_mut(n);
+    match plan.operation {
+        Operation::Forward => {
+            for (value, &datum) in buffer.iter_mut().zip(data.iter()) {
+                *value = datum.into();
+            }
+            compute_with(buffer, scratch, plan);
+            data[0] = buffer[0].re;
+            for i in 1..(h + 1) {
+                data[2 * i - 1] = buffer[i].re;
+                data[2 * i] = buffer[i].im;
+            }
+        }
+        Operation::Backward | Operation::Inverse => {
+            unpack_into(data, buffer);
+            compute_with(buffer, scratch, plan);
+            for (value, &complex) in data.iter_mut().zip(buffer.iter()) {
+                *value = complex.re;
+            }
+        }
     }
-    result
+}
+
+#[inline(always)]
+fn check<T>(n: usize, m: usize, plan: &Plan<T>) -> Result<()> {
+    if n != plan.n {
+        return Err(Error::LengthMismatch {
+            plan: plan.n,
+            data: n,
+        });
+    }
+    if m != (n >> 1) + 1 {
+        return Err(Error::LengthMismatch {
+            plan: plan.n,
+            data: m,
+        });
+    }
+    Ok(())
+}
+
+#[inline(always)]
+fn forward<T>(data: &mut [Complex<T>], plan: &Plan<T>, buffer: &mut Vec<Complex<T>>) -> Result<()>
+where
+    T: Float + FloatConst + 'static,
+{
+    let h = data.len();
+    let (factors, half) = plan.half()?;
+    compute_with(data, reserve(buffer, half.scratch()), half);
+    compose(data, h, factors, false);
+    Ok(())
+}
+
+#[inline(always)]
+fn backward<T>(data: &mut [Complex<T>], plan: &Plan<T>, buffer: &mut Vec<Complex<T>>) -> Result<()>
+where
+    T: Float + FloatConst + 'static,
+{
+    let h = data.len();
+    let (factors, half) = plan.half()?;
+    compose(data, h, factors, true);
+    compute_with(data, reserve(buffer, half.scratch()), half);
+    Ok(())
 }
 
 #[inline(always)]
@@ -73,26 +354,36 @@ where
     T: Float,
 {
     let one = T::one();
-    let half = (one + one).recip();
+    let two = one + one;
+    let factor = if inverse { one } else { two.recip() };
     let h = n >> 1;
     data[0] = Complex::new(data[0].re + data[0].im, data[0].re - data[0].im);
-    if inverse {
-        data[0] = data[0].scale(half);
-    }
     if h == 0 {
         return;
     }
     let m = factors.len();
     let sign: Complex<T> = if inverse { Complex::i() } else { -Complex::i() };
-    for i in 1..h {
+    for i in 1..((n + 1) >> 1) {
         let j = n - i;
         let part1 = data[i] + data[j].conj();
         let part2 = data[i] - data[j].conj();
         let product = sign * factors[m - j] * part2;
-        data[i] = (part1 + product).scale(half);
-        data[j] = (part1 - product).scale(half).conj();
+        data[i] = (part1 + product).scale(factor);
+        data[j] = (part1 - product).scale(factor).conj();
+    }
+    if n & 1 == 0 {
+        data[h] = data[h].conj().scale(two * factor);
+    }
+}
+
+#[inline(always)]
+fn scale<T>(data: &mut [T], factor: T)
+where
+    T: Float,
+{
+    for value in data {
+        *value = *value * factor;
     }
-    data[h] = data[h].conj();
 }
 
 #[cfg(test)]
@@ -126,5 +417,42 @@ mod tests {
                     c64::new(3.0, -4.0),
                 ]
         );
+
+        let data = (0..5).map(|i| (i + 1) as f64).collect::<Vec<_>>();
+        assert!(
+            super::unpack(&data)
+                == vec![
+                    c64::new(1.0, 0.0),
+                    c64::new(2.0, 3.0),
+                    c64::new(4.0, 5.0),
+                    c64::new(4.0, -5.0),
+                    c64::new(2.0, -3.0),
+                ]
+        );
+    }
+
+    #[test]
+    fn unpack_half_into() {
+        let data = (0..8).map(|i| (i + 1) as f64).collect::<Vec<_>>();
+        let mut result = vec![c64::new(0.0, 0.0); 5];
+        super::unpack_half_into(&data, &mut result);
+        assert!(
+            result
+                == vec![
+                    c64::new(1.0, 0.0),
+                    c64::new(3.0, 4.0),
+                    c64::new(5.0, 6.0),
+                    c64::new(7.0, 8.0),
+                    c64::new(2.0, 0.0),
+                ]
+        );
+    }
+
+    #[test]
+    fn pack() {
+        for n in 1..10 {
+            let data = (0..n).map(|i| (i + 1) as f64).collect::<Vec<_>>();
+            assert!(super::pack(&super::unpack(&data)) == data);
+        }
     }
 }
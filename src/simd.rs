@@ -0,0 +1,482 @@
+// The implementation vectorizes the butterflies of the kernel for powers of two
+// for `f32` and `f64` using SSE2, AVX, or AVX2 with FMA instructions, which are
+// detected at run time. The stages whose butterflies are fewer than the lanes
+// of a vector are left to the scalar code.
+
+use num_complex::Complex;
+use num_traits::{Float, One};
+use std::arch::x86_64::*;
+
+use complex::{quarters, radix2, radix4, SPLIT};
+use {is, Operation, Simd};
+
+trait Vector: Copy {
+    type Scalar: Float;
+
+    const LANES: usize;
+
+    unsafe fn load(pointer: *const Complex<Self::Scalar>) -> Self;
+    unsafe fn store(self, pointer: *mut Complex<Self::Scalar>);
+    unsafe fn add(self, other: Self) -> Self;
+    unsafe fn sub(self, other: Self) -> Self;
+    unsafe fn mul(self, other: Self) -> Self;
+    unsafe fn mask(forward: bool) -> Self;
+    unsafe fn rotate(self, mask: Self) -> Self;
+}
+
+#[derive(Clone, Copy)]
+struct Sse2F32(__m128);
+
+#[derive(Clone, Copy)]
+struct Sse2F64(__m128d);
+
+#[derive(Clone, Copy)]
+struct AvxF32(__m256);
+
+#[derive(Clone, Copy)]
+struct AvxF64(__m256d);
+
+#[derive(Clone, Copy)]
+struct FmaF32(__m256);
+
+#[derive(Clone, Copy)]
+struct FmaF64(__m256d);
+
+macro_rules! dispatch {
+    ($simd:expr, $sse2:ident, $avx:ident, $fma:ident) => {
+        match $simd {
+            Simd::Sse2 => $sse2,
+            Simd::Avx => $avx,
+            _ => $fma,
+        }
+    };
+}
+
+macro_rules! split {
+    ($split:ident, $merge:ident, $vector:ident, $features:literal) => {
+        #[target_feature(enable = $features)]
+        unsafe fn $split(
+            data: &mut [Complex<<$vector as Vector>::Scalar>],
+            factors: &[Complex<<$vector as Vector>::Scalar>],
+            forward: bool,
+        ) {
+            let n = data.len();
+            if n <= SPLIT {
+                calculate::<$vector>(data, n, factors, forward);
+                return;
+            }
+            let (h, q) = (n >> 1, n >> 2);
+            $split(&mut data[..h], factors, forward);
+            $split(&mut data[h..(h + q)], factors, forward);
+            $split(&mut data[(h + q)..], factors, forward);
+            let factors = [
+                &factors[(h - 1)..(h - 1 + q)],
+                &factors[(q - 1)..(2 * q - 1)],
+            ];
+            $merge(&mut quarters(data), &factors, forward);
+        }
+
+        #[target_feature(enable = $features)]
+        unsafe fn $merge(
+            quarters: &mut [&mut [Complex<<$vector as Vector>::Scalar>]; 4],
+            factors: &[&[Complex<<$vector as Vector>::Scalar>]; 2],
+            forward: bool,
+        ) {
+            merge::<$vector>(quarters, factors, forward);
+        }
+    };
+}
+
+/// Perform the butterflies of the kernel for powers of two on bit-reversed data
+/// if the type of the data and the instructions available allow for it.
+pub fn split<T>(
+    data: &mut [Complex<T>],
+    factors: &[Complex<T>],
+    operation: Operation,
+    simd: Simd,
+) -> bool
+where
+    T: Float + 'static,
+{
+    let simd = match detect(simd) {
+        Some(simd) => simd,
+        _ => return false,
+    };
+    let forward = operation == Operation::Forward;
+    unsafe {
+        if is::<T, f32>() {
+            let data = &mut *(data as *mut [Complex<T>] as *mut [Complex<f32>]);
+            let factors = &*(factors as *const [Complex<T>] as *const [Complex<f32>]);
+            dispatch!(simd, split_sse2_f32, split_avx_f32, split_fma_f32)(data, factors, forward);
+        } else if is::<T, f64>() {
+            let data = &mut *(data as *mut [Complex<T>] as *mut [Complex<f64>]);
+            let factors = &*(factors as *const [Complex<T>] as *const [Complex<f64>]);
+            dispatch!(simd, split_sse2_f64, split_avx_f64, split_fma_f64)(data, factors, forward);
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+/// Perform a split-radix step on the quarters of bit-reversed data if the type
+/// of the data and the instructions available allow for it.
+#[cfg(feature = "parallel")]
+pub fn combine<T>(
+    quarters: &mut [&mut [Complex<T>]; 4],
+    factors: &[&[Complex<T>]; 2],
+    operation: Operation,
+    simd: Simd,
+) -> bool
+where
+    T: Float + 'static,
+{
+    let simd = match detect(simd) {
+        Some(simd) => simd,
+        _ => return false,
+    };
+    let forward = operation == Operation::Forward;
+    unsafe {
+        if is::<T, f32>() {
+            let quarters = &mut *(quarters as *mut _ as *mut [&mut [Complex<f32>]; 4]);
+            let factors = &*(factors as *const _ as *const [&[Complex<f32>]; 2]);
+            dispatch!(simd, merge_sse2_f32, merge_avx_f32, merge_fma_f32)(
+                quarters, factors, forward,
+            );
+        } else if is::<T, f64>() {
+            let quarters = &mut *(quarters as *mut _ as *mut [&mut [Complex<f64>]; 4]);
+            let factors = &*(factors as *const _ as *const [&[Complex<f64>]; 2]);
+            dispatch!(simd, merge_sse2_f64, merge_avx_f64, merge_fma_f64)(
+                quarters, factors, forward,
+            );
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+fn detect(simd: Simd) -> Option<Simd> {
+    match simd {
+        Simd::None => None,
+        Simd::Detect => [Simd::Avx2, Simd::Avx, Simd::Sse2]
+            .iter()
+            .cloned()
+            .find(|&simd| supported(simd)),
+        _ if supported(simd) => Some(simd),
+        _ => None,
+    }
+}
+
+fn supported(simd: Simd) -> bool {
+    match simd {
+        Simd::Sse2 => is_x86_feature_detected!("sse2"),
+        Simd::Avx => is_x86_feature_detected!("avx"),
+        Simd::Avx2 => is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma"),
+        _ => false,
+    }
+}
+
+split!(split_sse2_f32, merge_sse2_f32, Sse2F32, "sse2");
+split!(split_sse2_f64, merge_sse2_f64, Sse2F64, "sse2");
+split!(split_avx_f32, merge_avx_f32, AvxF32, "avx");
+split!(split_avx_f64, merge_avx_f64, AvxF64, "avx");
+split!(split_fma_f32, merge_fma_f32, FmaF32, "avx2,fma");
+split!(split_fma_f64, merge_fma_f64, FmaF64, "avx2,fma");
+
+#[inline(always)]
+unsafe fn calculate<V>(
+    data: &mut [Complex<V::Scalar>],
+    n: usize,
+    factors: &[Complex<V::Scalar>],
+    forward: bool,
+) where
+    V: Vector,
+{
+    let one = V::Scalar::one();
+    let sign = if forward { -one } else { one };
+    let mask = V::mask(forward);
+    let mut step = 1;
+    if n.trailing_zeros() & 1 == 1 {
+        radix2(data, n);
+        step = 2;
+    }
+    while step < n {
+        if step < V::LANES {
+            radix4(data, n, step, factors, sign);
+            step <<= 2;
+            continue;
+        }
+        let (data, factors) = (data.as_mut_ptr(), factors.as_ptr());
+        let jump = step << 2;
+        let mut k = 0;
+        while k < step {
+            let factor1 = V::load(factors.add(step - 1 + k));
+            let factor2 = V::load(factors.add(2 * step - 1 + k));
+            let factor3 = factor1.mul(factor2);
+            let mut i = k;
+            while i < n {
+                let x0 = V::load(data.add(i));
+                let x1 = V::load(data.add(i + step)).mul(factor1);
+                let x2 = V::load(data.add(i + 2 * step)).mul(factor2);
+                let x3 = V::load(data.add(i + 3 * step)).mul(factor3);
+                let (sum1, difference1) = (x0.add(x1), x0.sub(x1));
+                let (sum2, difference2) = (x2.add(x3), x2.sub(x3).rotate(mask));
+                sum1.add(sum2).store(data.add(i));
+                difference1.add(difference2).store(data.add(i + step));
+                sum1.sub(sum2).store(data.add(i + 2 * step));
+                difference1.sub(difference2).store(data.add(i + 3 * step));
+                i += jump;
+            }
+            k += V::LANES;
+        }
+        step <<= 2;
+    }
+}
+
+#[inline(always)]
+unsafe fn merge<V>(
+    quarters: &mut [&mut [Complex<V::Scalar>]; 4],
+    factors: &[&[Complex<V::Scalar>]; 2],
+    forward: bool,
+) where
+    V: Vector,
+{
+    let mask = V::mask(forward);
+    let q = quarters[0].len();
+    let [ref mut x0, ref mut x1, ref mut z1, ref mut z3] = *quarters;
+    let (x0, x1) = (x0.as_mut_ptr(), x1.as_mut_ptr());
+    let (z1, z3) = (z1.as_mut_ptr(), z3.as_mut_ptr());
+    let (factors1, factors2) = (factors[0].as_ptr(), factors[1].as_ptr());
+    let mut k = 0;
+    while k < q {
+        let factor1 = V::load(factors1.add(k));
+        let factor3 = factor1.mul(V::load(factors2.add(k)));
+        let y1 = V::load(z1.add(k)).mul(factor1);
+        let y3 = V::load(z3.add(k)).mul(factor3);
+        let (sum, difference) = (y1.add(y3), y1.sub(y3).rotate(mask));
+        let (y0, y2) = (V::load(x0.add(k)), V::load(x1.add(k)));
+        y0.add(sum).store(x0.add(k));
+        y0.sub(sum).store(z1.add(k));
+        y2.add(difference).store(x1.add(k));
+        y2.sub(difference).store(z3.add(k));
+        k += V::LANES;
+    }
+}
+
+impl Vector for Sse2F32 {
+    type Scalar = f32;
+
+    const LANES: usize = 2;
+
+    #[inline(always)]
+    unsafe fn load(pointer: *const Complex<f32>) -> Self {
+        Sse2F32(_mm_loadu_ps(pointer as *const f32))
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, pointer: *mut Complex<f32>) {
+        _mm_storeu_ps(pointer as *mut f32, self.0)
+    }
+
+    #[inline(always)]
+    unsafe fn add(self, other: Self) -> Self {
+        Sse2F32(_mm_add_ps(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn sub(self, other: Self) -> Self {
+        Sse2F32(_mm_sub_ps(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn mul(self, other: Self) -> Self {
+        let re = _mm_shuffle_ps(other.0, other.0, 0b10_10_00_00);
+        let im = _mm_shuffle_ps(other.0, other.0, 0b11_11_01_01);
+        let swapped = _mm_shuffle_ps(self.0, self.0, 0b10_11_00_01);
+        let product = _mm_mul_ps(swapped, im);
+        let product = _mm_xor_ps(product, _mm_set_ps(0.0, -0.0, 0.0, -0.0));
+        Sse2F32(_mm_add_ps(_mm_mul_ps(self.0, re), product))
+    }
+
+    #[inline(always)]
+    unsafe fn mask(forward: bool) -> Self {
+        Sse2F32(if forward {
+            _mm_set_ps(-0.0, 0.0, -0.0, 0.0)
+        } else {
+            _mm_set_ps(0.0, -0.0, 0.0, -0.0)
+        })
+    }
+
+    #[inline(always)]
+    unsafe fn rotate(self, mask: Self) -> Self {
+        let swapped = _mm_shuffle_ps(self.0, self.0, 0b10_11_00_01);
+        Sse2F32(_mm_xor_ps(swapped, mask.0))
+    }
+}
+
+impl Vector for Sse2F64 {
+    type Scalar = f64;
+
+    const LANES: usize = 1;
+
+    #[inline(always)]
+    unsafe fn load(pointer: *const Complex<f64>) -> Self {
+        Sse2F64(_mm_loadu_pd(pointer as *const f64))
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, pointer: *mut Complex<f64>) {
+        _mm_storeu_pd(pointer as *mut f64, self.0)
+    }
+
+    #[inline(always)]
+    unsafe fn add(self, other: Self) -> Self {
+        Sse2F64(_mm_add_pd(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn sub(self, other: Self) -> Self {
+        Sse2F64(_mm_sub_pd(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn mul(self, other: Self) -> Self {
+        let re = _mm_unpacklo_pd(other.0, other.0);
+        let im = _mm_unpackhi_pd(other.0, other.0);
+        let swapped = _mm_shuffle_pd(self.0, self.0, 0b01);
+        let product = _mm_mul_pd(swapped, im);
+        let product = _mm_xor_pd(product, _mm_set_pd(0.0, -0.0));
+        Sse2F64(_mm_add_pd(_mm_mul_pd(self.0, re), product))
+    }
+
+    #[inline(always)]
+    unsafe fn mask(forward: bool) -> Self {
+        Sse2F64(if forward {
+            _mm_set_pd(-0.0, 0.0)
+        } else {
+            _mm_set_pd(0.0, -0.0)
+        })
+    }
+
+    #[inline(always)]
+    unsafe fn rotate(self, mask: Self) -> Self {
+        let swapped = _mm_shuffle_pd(self.0, self.0, 0b01);
+        Sse2F64(_mm_xor_pd(swapped, mask.0))
+    }
+}
+
+macro_rules! avx {
+    ($f32:ident, $f64:ident, $mul_f32:ident, $mul_f64:ident) => {
+        impl Vector for $f32 {
+            type Scalar = f32;
+
+            const LANES: usize = 4;
+
+            #[inline(always)]
+            unsafe fn load(pointer: *const Complex<f32>) -> Self {
+                $f32(_mm256_loadu_ps(pointer as *const f32))
+            }
+
+            #[inline(always)]
+            unsafe fn store(self, pointer: *mut Complex<f32>) {
+                _mm256_storeu_ps(pointer as *mut f32, self.0)
+            }
+
+            #[inline(always)]
+            unsafe fn add(self, other: Self) -> Self {
+                $f32(_mm256_add_ps(self.0, other.0))
+            }
+
+            #[inline(always)]
+            unsafe fn sub(self, other: Self) -> Self {
+                $f32(_mm256_sub_ps(self.0, other.0))
+            }
+
+            #[inline(always)]
+            unsafe fn mul(self, other: Self) -> Self {
+                let re = _mm256_moveldup_ps(other.0);
+                let im = _mm256_movehdup_ps(other.0);
+                let swapped = _mm256_permute_ps(self.0, 0b10_11_00_01);
+                $f32($mul_f32(self.0, re, _mm256_mul_ps(swapped, im)))
+            }
+
+            #[inline(always)]
+            unsafe fn mask(forward: bool) -> Self {
+                $f32(if forward {
+                    _mm256_set_ps(-0.0, 0.0, -0.0, 0.0, -0.0, 0.0, -0.0, 0.0)
+                } else {
+                    _mm256_set_ps(0.0, -0.0, 0.0, -0.0, 0.0, -0.0, 0.0, -0.0)
+                })
+            }
+
+            #[inline(always)]
+            unsafe fn rotate(self, mask: Self) -> Self {
+                let swapped = _mm256_permute_ps(self.0, 0b10_11_00_01);
+                $f32(_mm256_xor_ps(swapped, mask.0))
+            }
+        }
+
+        impl Vector for $f64 {
+            type Scalar = f64;
+
+            const LANES: usize = 2;
+
+            #[inline(always)]
+            unsafe fn load(pointer: *const Complex<f64>) -> Self {
+                $f64(_mm256_loadu_pd(pointer as *const f64))
+            }
+
+            #[inline(always)]
+            unsafe fn store(self, pointer: *mut Complex<f64>) {
+                _mm256_storeu_pd(pointer as *mut f64, self.0)
+            }
+
+            #[inline(always)]
+            unsafe fn add(self, other: Self) -> Self {
+                $f64(_mm256_add_pd(self.0, other.0))
+            }
+
+            #[inline(always)]
+            unsafe fn sub(self, other: Self) -> Self {
+                $f64(_mm256_sub_pd(self.0, other.0))
+            }
+
+            #[inline(always)]
+            unsafe fn mul(self, other: Self) -> Self {
+                let re = _mm256_movedup_pd(other.0);
+                let im = _mm256_permute_pd(other.0, 0b1111);
+                let swapped = _mm256_permute_pd(self.0, 0b0101);
+                $f64($mul_f64(self.0, re, _mm256_mul_pd(swapped, im)))
+            }
+
+            #[inline(always)]
+            unsafe fn mask(forward: bool) -> Self {
+                $f64(if forward {
+                    _mm256_set_pd(-0.0, 0.0, -0.0, 0.0)
+                } else {
+                    _mm256_set_pd(0.0, -0.0, 0.0, -0.0)
+                })
+            }
+
+            #[inline(always)]
+            unsafe fn rotate(self, mask: Self) -> Self {
+                let swapped = _mm256_permute_pd(self.0, 0b0101);
+                $f64(_mm256_xor_pd(swapped, mask.0))
+            }
+        }
+    };
+}
+
+avx!(AvxF32, AvxF64, mul_addsub_f32, mul_addsub_f64);
+avx!(FmaF32, FmaF64, _mm256_fmaddsub_ps, _mm256_fmaddsub_pd);
+
+#[inline(always)]
+unsafe fn mul_addsub_f32(a: __m256, b: __m256, c: __m256) -> __m256 {
+    _mm256_addsub_ps(_mm256_mul_ps(a, b), c)
+}
+
+#[inline(always)]
+unsafe fn mul_addsub_f64(a: __m256d, b: __m256d, c: __m256d) -> __m256d {
+    _mm256_addsub_pd(_mm256_mul_pd(a, b), c)
+}
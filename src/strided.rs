@@ -0,0 +1,119 @@
+use num_complex::Complex;
+use num_traits::{Float, FloatConst};
+
+use {Error, Plan, Result, Transform};
+
+/// A strided view of a slice.
+///
+/// The view consists of the elements `data[offset + i * stride]` for `i` from
+/// zero up to the length, which makes it possible to transform, for instance,
+/// one channel of an interleaved multichannel signal without gathering it
+/// manually. The elements are gathered into a buffer owned by the view, which
+/// is allocated on the first transform and reused by the subsequent ones.
+pub struct Strided<'l, T: 'l> {
+    data: &'l mut [T],
+    offset: usize,
+    stride: usize,
+    length: usize,
+    buffer: Vec<T>,
+}
+
+impl<'l, T> Strided<'l, T> {
+    /// Create a view.
+    ///
+    /// The function panics if the view is not supported; see
+    /// `Strided::try_new`.
+    pub fn new(data: &'l mut [T], offset: usize, stride: usize, length: usize) -> Self {
+        match Strided::try_new(data, offset, stride, length) {
+            Ok(view) => view,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Create a view if the stride is positive and the view fits in the data.
+    pub fn try_new(data: &'l mut [T], offset: usize, stride: usize, length: usize) -> Result<Self> {
+        if stride == 0 {
+            return Err(Error::UnsupportedStride(stride));
+        }
+        let view = match length {
+            0 => offset,
+            _ => (length - 1)
+                .checked_mul(stride)
+                .and_then(|span| span.checked_add(offset))
+                .and_then(|last| last.checked_add(1))
+                .unwrap_or(usize::MAX),
+        };
+        if view > data.len() {
+            return Err(Error::ViewOutOfBounds {
+                view,
+                data: data.len(),
+            });
+        }
+        Ok(Strided {
+            data,
+            offset,
+            stride,
+            length,
+            buffer: Vec::new(),
+        })
+    }
+
+    fn gather(&mut self)
+    where
+        T: Copy,
+    {
+        let data = self.data[self.offset..].iter().step_by(self.stride);
+        self.buffer.clear();
+        self.buffer.extend(data.take(self.length).cloned());
+    }
+
+    fn scatter(&mut self)
+    where
+        T: Copy,
+    {
+        let data = self.data[self.offset..].iter_mut().step_by(self.stride);
+        for (value, &other) in data.zip(&self.buffer) {
+            *value = other;
+        }
+    }
+}
+
+impl<'l, T> Transform<T> for Strided<'l, Complex<T>>
+where
+    T: Float + 'static,
+{
+    fn try_transform(&mut self, plan: &Plan<T>) -> Result<()> {
+        self.try_transform_with_buffer(plan, &mut Vec::new())
+    }
+
+    fn try_transform_with_buffer(
+        &mut self,
+        plan: &Plan<T>,
+        buffer: &mut Vec<Complex<T>>,
+    ) -> Result<()> {
+        self.gather();
+        self.buffer[..].try_transform_with_buffer(plan, buffer)?;
+        self.scatter();
+        Ok(())
+    }
+}
+
+impl<'l, T> Transform<T> for Strided<'l, T>
+where
+    T: Float + FloatConst + 'static,
+{
+    fn try_transform(&mut self, plan: &Plan<T>) -> Result<()> {
+        self.try_transform_with_buffer(plan, &mut Vec::new())
+    }
+
+    fn try_transform_with_buffer(
+        &mut self,
+        plan: &Plan<T>,
+        buffer: &mut Vec<Complex<T>>,
+    ) -> Result<()> {
+        self.gather();
+        self.buffer[..].try_transform_with_buffer(plan, buffer)?;
+        self.scatter();
+        Ok(())
+    }
+}
@@ -1,3 +1,6 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(clippy::excessive_precision)]
+
 pub const TIME_DATA_256: [f64; 256] = [
     0.242578298897752, 0.013469574513598, 0.383138850044058, 0.414652690484492,
     0.067768972864267, 0.993126929734427, 0.484308046514312, 0.765337766038877,
@@ -1,7 +1,12 @@
 extern crate assert;
 extern crate dft;
 
-use dft::{transform, unpack, Operation, Plan, c64};
+use dft::{
+    c2r, dct, dst, hartley, ntt, pack, pack_hartley, r2c, transform, transform_with_scratch,
+    try_r2c, try_transform, try_transform_with_scratch, unpack, unpack2, unpack_hartley, Error,
+    Kernel, Mdct, Normalization, Operation, Options, Plan, Plan2, PlanN, Simd, Strided, Twiddles,
+    Window, c32, c64,
+};
 
 mod fixtures;
 
@@ -44,6 +49,849 @@ fn complex_inverse_128() {
     assert::close(&data, &fixtures::TIME_DATA_256[..], 1e-14);
 }
 
+#[test]
+fn complex_normalization() {
+    for &n in &[16, 15, 23] {
+        let data = signal(n);
+        let expected = naive(&data, Operation::Forward);
+        let energy = |data: &[c64]| data.iter().map(|value| value.norm_sqr()).sum::<f64>();
+        let plan = |operation, normalization| {
+            let options = Options {
+                normalization,
+                ..Options::default()
+            };
+            Plan::with_options(operation, n, options)
+        };
+
+        let mut result = data.clone();
+        transform(
+            &mut result,
+            &plan(Operation::Forward, Normalization::Orthonormal),
+        );
+        assert::close(energy(&result), energy(&data), 1e-10);
+        transform(
+            &mut result,
+            &plan(Operation::Inverse, Normalization::Orthonormal),
+        );
+        assert::close(as_f64(&result), as_f64(&data), 1e-12);
+
+        let mut result = data.clone();
+        transform(
+            &mut result,
+            &plan(Operation::Forward, Normalization::Forward),
+        );
+        let scaled = expected
+            .iter()
+            .map(|value| value / n as f64)
+            .collect::<Vec<_>>();
+        assert::close(as_f64(&result), as_f64(&scaled), 1e-12);
+        transform(
+            &mut result,
+            &plan(Operation::Inverse, Normalization::Forward),
+        );
+        assert::close(as_f64(&result), as_f64(&data), 1e-12);
+
+        let mut result = expected.clone();
+        transform(&mut result, &plan(Operation::Inverse, Normalization::None));
+        let scaled = data
+            .iter()
+            .map(|value| value * n as f64)
+            .collect::<Vec<_>>();
+        assert::close(as_f64(&result), as_f64(&scaled), 1e-9);
+    }
+}
+
+#[test]
+fn complex_forward_radix2() {
+    for &n in &[2, 4, 8, 32, 64, 512, 1024, 2048, 4096] {
+        let data = signal(n);
+        let mut result = data.clone();
+        transform(&mut result, &Plan::new(Operation::Forward, n));
+        assert::close(
+            as_f64(&result),
+            as_f64(&naive(&data, Operation::Forward)),
+            1e-9,
+        );
+        transform(&mut result, &Plan::new(Operation::Inverse, n));
+        assert::close(as_f64(&result), as_f64(&data), 1e-12);
+    }
+}
+
+#[test]
+fn complex_stockham() {
+    let options = Options {
+        kernel: Kernel::Stockham,
+        ..Options::default()
+    };
+    for &operation in &[Operation::Forward, Operation::Inverse] {
+        let plan = Plan::with_options(operation, 4096, options);
+        let mut scratch = vec![c64::new(0.0, 0.0); 4096];
+        for &n in &[1, 2, 4, 8, 32, 64, 512, 1024, 2048, 4096] {
+            let data = signal(n);
+            let mut expected = data.clone();
+            transform(&mut expected, &Plan::new(operation, n));
+            let mut result = data.clone();
+            transform(&mut result, &plan);
+            assert::close(as_f64(&result), as_f64(&expected), 1e-10);
+            let mut result = data.clone();
+            transform_with_scratch(&mut result, &mut scratch, &plan);
+            assert::close(as_f64(&result), as_f64(&expected), 1e-10);
+        }
+    }
+}
+
+#[test]
+fn complex_simd() {
+    let mut instructions = vec![Simd::Detect];
+    for &simd in &[Simd::Sse2, Simd::Avx, Simd::Avx2] {
+        if supported(simd) {
+            instructions.push(simd);
+        } else {
+            eprintln!("skipping {:?}: not supported by the processor", simd);
+        }
+    }
+    let sizes = (0..14)
+        .map(|i| 1 << i)
+        .chain(vec![3, 6, 15, 22, 23, 47, 83, 1000, 2689, 4099, 44100]);
+    for n in sizes {
+        let data = signal(n);
+        let data32 = data
+            .iter()
+            .map(|value| c32::new(value.re as f32, value.im as f32))
+            .collect::<Vec<_>>();
+        let norm = data32
+            .iter()
+            .map(|value| value.norm_sqr())
+            .sum::<f32>()
+            .sqrt();
+        for &operation in &[Operation::Forward, Operation::Inverse] {
+            let options = |simd| Options {
+                simd,
+                ..Options::default()
+            };
+            let mut expected = data.clone();
+            transform(
+                &mut expected,
+                &Plan::with_options(operation, n, options(Simd::None)),
+            );
+            let mut expected32 = data32.clone();
+            transform(
+                &mut expected32,
+                &Plan::with_options(operation, n, options(Simd::None)),
+            );
+            for &simd in &instructions {
+                let mut result = data.clone();
+                transform(
+                    &mut result,
+                    &Plan::with_options(operation, n, options(simd)),
+                );
+                assert::close(as_f64(&result), as_f64(&expected), 1e-10);
+                let mut result = data32.clone();
+                transform(
+                    &mut result,
+                    &Plan::with_options(operation, n, options(simd)),
+                );
+                for (result, expected) in result.iter().zip(&expected32) {
+                    assert!((result - expected).norm() <= 1e-5 * norm);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn complex_parallel() {
+    for &n in &[1 << 14, 1 << 15, 1 << 18] {
+        let data = signal(n);
+        let data32 = data
+            .iter()
+            .map(|value| c32::new(value.re as f32, value.im as f32))
+            .collect::<Vec<_>>();
+        for &operation in &[Operation::Forward, Operation::Inverse] {
+            let options = |threads| Options {
+                threads,
+                ..Options::default()
+            };
+            let mut expected = data.clone();
+            transform(&mut expected, &Plan::with_options(operation, n, options(1)));
+            let mut expected32 = data32.clone();
+            transform(
+                &mut expected32,
+                &Plan::with_options(operation, n, options(1)),
+            );
+            for &threads in &[2, 3, 4, 8] {
+                let mut result = data.clone();
+                transform(
+                    &mut result,
+                    &Plan::with_options(operation, n, options(threads)),
+                );
+                assert_eq!(result, expected);
+                let mut result = data32.clone();
+                transform(
+                    &mut result,
+                    &Plan::with_options(operation, n, options(threads)),
+                );
+                assert_eq!(result, expected32);
+            }
+        }
+    }
+}
+
+#[test]
+fn complex_four_step() {
+    let options = Options {
+        four_step: Some(1000),
+        ..Options::default()
+    };
+    for &n in &[1 << 12, 1 << 15, 3 * 4099, 44100, 4099] {
+        let data = signal(n);
+        for &operation in &[Operation::Forward, Operation::Inverse] {
+            let mut expected = data.clone();
+            transform(&mut expected, &Plan::new(operation, n));
+            let mut result = data.clone();
+            transform(&mut result, &Plan::with_options(operation, n, options));
+            assert::close(as_f64(&result), as_f64(&expected), 1e-8);
+        }
+    }
+}
+
+#[test]
+fn complex_batch() {
+    let cases = [
+        (8, 5, 8),
+        (15, 4, 20),
+        (1024, 3, 1030),
+        (22, 3, 25),
+        (29, 3, 29),
+    ];
+    for &(n, count, distance) in &cases {
+        let data = signal(count * distance);
+        for &operation in &[Operation::Forward, Operation::Inverse] {
+            let plan = Plan::new(operation, n);
+            let mut expected = data.clone();
+            for chunk in expected.chunks_mut(distance) {
+                transform(&mut chunk[..n], &plan);
+            }
+            let mut result = data.clone();
+            plan.transform_batch(&mut result, count, distance);
+            assert_eq!(result, expected);
+        }
+    }
+}
+
+#[test]
+fn complex_strided() {
+    let (n, channels) = (15, 3);
+    let data = signal(n * channels + 1);
+    for channel in 0..channels {
+        let plan = Plan::new(Operation::Forward, n);
+        let mut expected = data.clone();
+        let mut gathered = (0..n)
+            .map(|i| data[1 + channel + i * channels])
+            .collect::<Vec<_>>();
+        transform(&mut gathered, &plan);
+        for (i, value) in gathered.into_iter().enumerate() {
+            expected[1 + channel + i * channels] = value;
+        }
+        let mut result = data.clone();
+        transform(
+            &mut Strided::new(&mut result, 1 + channel, channels, n),
+            &plan,
+        );
+        assert_eq!(result, expected);
+        {
+            let inverse = Plan::new(Operation::Inverse, n);
+            let mut view = Strided::new(&mut result, 1 + channel, channels, n);
+            transform(&mut view, &inverse);
+            transform(&mut view, &plan);
+            transform(&mut view, &inverse);
+        }
+        assert::close(as_f64(&result), as_f64(&data), 1e-12);
+    }
+}
+
+#[test]
+fn complex_forward_shorter() {
+    let plan = Plan::new(Operation::Forward, 128);
+    for &n in &[1, 2, 4, 16, 64] {
+        let data = signal(n);
+        let mut result = data.clone();
+        transform(&mut result, &plan);
+        assert::close(
+            as_f64(&result),
+            as_f64(&naive(&data, Operation::Forward)),
+            1e-12,
+        );
+    }
+    let mut data = signal(48);
+    assert_eq!(
+        try_transform(&mut data, &plan),
+        Err(Error::LengthMismatch {
+            plan: 128,
+            data: 48
+        })
+    );
+}
+
+#[test]
+fn complex_inverse_shorter() {
+    let data = signal(64);
+    let mut result = data.clone();
+    transform(&mut result, &Plan::new(Operation::Forward, 256));
+    transform(&mut result, &Plan::new(Operation::Inverse, 1024));
+    assert::close(as_f64(&result), as_f64(&data), 1e-14);
+}
+
+#[test]
+fn complex_forward_mixed() {
+    for &n in &[3, 5, 6, 7, 12, 15, 30, 35, 49, 60, 105, 210, 1000] {
+        let data = signal(n);
+        let mut result = data.clone();
+        transform(&mut result, &Plan::new(Operation::Forward, n));
+        assert::close(
+            as_f64(&result),
+            as_f64(&naive(&data, Operation::Forward)),
+            1e-10,
+        );
+    }
+}
+
+#[test]
+fn complex_forward_direct() {
+    let options = Options {
+        twiddles: Twiddles::Direct,
+        ..Options::default()
+    };
+    for &n in &[8, 1024, 24, 1000, 23, 29] {
+        let data = signal(n);
+        let mut result = data.clone();
+        transform(
+            &mut result,
+            &Plan::with_options(Operation::Forward, n, options),
+        );
+        assert::close(
+            as_f64(&result),
+            as_f64(&naive(&data, Operation::Forward)),
+            1e-10,
+        );
+    }
+}
+
+#[test]
+fn complex_inverse_mixed() {
+    for &n in &[1000, 44100, 48000] {
+        let data = signal(n);
+        let mut result = data.clone();
+        transform(&mut result, &Plan::new(Operation::Forward, n));
+        transform(&mut result, &Plan::new(Operation::Inverse, n));
+        assert::close(as_f64(&result), as_f64(&data), 1e-12);
+    }
+}
+
+#[test]
+fn complex_forward_bluestein() {
+    for &n in &[22, 23, 26, 47, 83, 4099] {
+        let data = signal(n);
+        let mut result = data.clone();
+        transform(&mut result, &Plan::new(Operation::Forward, n));
+        assert::close(
+            as_f64(&result),
+            as_f64(&naive(&data, Operation::Forward)),
+            1e-10,
+        );
+    }
+}
+
+#[test]
+fn complex_inverse_bluestein() {
+    for &n in &[4099, 2 * 4099] {
+        let data = signal(n);
+        let mut result = data.clone();
+        transform(&mut result, &Plan::new(Operation::Forward, n));
+        transform(&mut result, &Plan::new(Operation::Inverse, n));
+        assert::close(as_f64(&result), as_f64(&data), 1e-12);
+    }
+}
+
+#[test]
+fn complex_forward_rader() {
+    let primes = [
+        11, 13, 17, 19, 29, 31, 37, 41, 43, 61, 71, 73, 97, 113, 127, 257, 337, 421, 631, 1009,
+        2017, 2689,
+    ];
+    for &n in &primes {
+        let data = signal(n);
+        let mut result = data.clone();
+        transform(&mut result, &Plan::new(Operation::Forward, n));
+        assert::close(
+            as_f64(&result),
+            as_f64(&naive(&data, Operation::Forward)),
+            1e-9,
+        );
+    }
+}
+
+#[test]
+fn complex_inverse_rader() {
+    for &n in &[11, 1009, 2689] {
+        let data = signal(n);
+        let mut result = data.clone();
+        transform(&mut result, &Plan::new(Operation::Forward, n));
+        transform(&mut result, &Plan::new(Operation::Inverse, n));
+        assert::close(as_f64(&result), as_f64(&data), 1e-12);
+    }
+}
+
+#[test]
+fn complex_forward_2d() {
+    for &(rows, columns) in &[(1, 1), (4, 6), (5, 7), (8, 8), (3, 17), (20, 33)] {
+        let data = signal(rows * columns);
+        let mut result = data.clone();
+        Plan2::new(Operation::Forward, rows, columns).transform(&mut result);
+        assert::close(
+            as_f64(&result),
+            as_f64(&naive2(&data, rows, columns, Operation::Forward)),
+            1e-10,
+        );
+        Plan2::new(Operation::Inverse, rows, columns).transform(&mut result);
+        assert::close(as_f64(&result), as_f64(&data), 1e-12);
+    }
+}
+
+#[test]
+fn complex_forward_nd() {
+    let cases: &[(&[usize], &[usize])] = &[
+        (&[6], &[0]),
+        (&[4, 6], &[0, 1]),
+        (&[2, 3, 4], &[0, 1, 2]),
+        (&[3, 20, 5], &[2, 0]),
+        (&[4, 4, 4], &[1, 2]),
+        (&[2, 3, 2, 5], &[1, 3]),
+    ];
+    for &(shape, axes) in cases {
+        let data = signal(shape.iter().product());
+        let mut expected = data.clone();
+        for &axis in axes {
+            expected = naive_axis(&expected, shape, axis, Operation::Forward);
+        }
+        let mut result = data.clone();
+        PlanN::new(Operation::Forward, shape, axes).transform(&mut result);
+        assert::close(as_f64(&result), as_f64(&expected), 1e-10);
+        PlanN::new(Operation::Inverse, shape, axes).transform(&mut result);
+        assert::close(as_f64(&result), as_f64(&data), 1e-12);
+    }
+}
+
+#[test]
+fn complex_forward_nd_empty() {
+    let plan = PlanN::<f64>::new(Operation::Forward, &[4, 0], &[0]);
+    assert_eq!(plan.try_transform(&mut []), Ok(()));
+}
+
+#[test]
+fn dct_forward() {
+    for &kind in &[dct::Type::I, dct::Type::II, dct::Type::III, dct::Type::IV] {
+        for n in (1..33).chain(vec![64, 100, 127, 256]) {
+            if kind == dct::Type::I && n < 2 {
+                continue;
+            }
+            let data = (0..n)
+                .map(|i| ((i * 7) % 11) as f64 - 5.0)
+                .collect::<Vec<_>>();
+            for &normalization in &NORMALIZATIONS {
+                let mut result = data.clone();
+                dct::Plan::with_normalization(kind, n, normalization).transform(&mut result);
+                assert::close(
+                    &result,
+                    naive_dct(&data, kind, normalization),
+                    1e-10 * n as f64,
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn dct_orthonormal() {
+    use dct::Type::{I, II, III, IV};
+    use dft::Normalization::Orthonormal;
+
+    let pairs = &[(I, I), (II, III), (III, II), (IV, IV)];
+    for &(forward, backward) in pairs {
+        for &n in &[2, 5, 8, 63, 64] {
+            let data = (0..n).map(|i| (i as f64).sin()).collect::<Vec<_>>();
+            let mut result = data.clone();
+            dct::Plan::with_normalization(forward, n, Orthonormal).transform(&mut result);
+            dct::Plan::with_normalization(backward, n, Orthonormal).transform(&mut result);
+            assert::close(&result, &data, 1e-12);
+        }
+    }
+}
+
+#[test]
+fn dst_forward() {
+    for &kind in &[dst::Type::I, dst::Type::II, dst::Type::III, dst::Type::IV] {
+        for n in (1..33).chain(vec![64, 100, 127, 256]) {
+            let data = (0..n)
+                .map(|i| ((i * 7) % 11) as f64 - 5.0)
+                .collect::<Vec<_>>();
+            for &normalization in &NORMALIZATIONS {
+                let mut result = data.clone();
+                dst::Plan::with_normalization(kind, n, normalization).transform(&mut result);
+                assert::close(
+                    &result,
+                    naive_dst(&data, kind, normalization),
+                    1e-10 * n as f64,
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn dst_inverse() {
+    use dft::Normalization::{None, Orthonormal};
+    use dst::Type::{I, II, III, IV};
+
+    let pairs = &[(I, I), (II, III), (III, II), (IV, IV)];
+    for &(forward, backward) in pairs {
+        for &n in &[1, 2, 5, 8, 63, 64] {
+            let data = (0..n).map(|i| (i as f64).sin()).collect::<Vec<_>>();
+            let mut result = data.clone();
+            dst::Plan::with_normalization(forward, n, Orthonormal).transform(&mut result);
+            dst::Plan::with_normalization(backward, n, Orthonormal).transform(&mut result);
+            assert::close(&result, &data, 1e-12);
+
+            let mut result = data.clone();
+            dst::Plan::with_normalization(forward, n, None).transform(&mut result);
+            dst::Plan::with_normalization(backward, n, None).transform(&mut result);
+            let m = if forward == I { n + 1 } else { n };
+            let result = result
+                .iter()
+                .map(|&value| value * 2.0 / m as f64)
+                .collect::<Vec<_>>();
+            assert::close(&result, &data, 1e-12);
+        }
+    }
+}
+
+#[test]
+fn mdct_forward() {
+    use std::f64::consts::PI;
+
+    for &n in &[2, 4, 16, 30, 64] {
+        for &window in &[Window::Sine, Window::KaiserBessel(4.0)] {
+            let data = (0..(2 * n))
+                .map(|i| ((i * 7) % 11) as f64 - 5.0)
+                .collect::<Vec<_>>();
+            let weights = (0..(2 * n))
+                .map(|j| window_value(window, n, j))
+                .collect::<Vec<_>>();
+            let mut result = vec![0.0; n];
+            Mdct::new(n, window).forward(&data, &mut result);
+            let expected = (0..n)
+                .map(|k| {
+                    (0..(2 * n))
+                        .map(|j| {
+                            let angle = PI / n as f64
+                                * (j as f64 + 0.5 + n as f64 / 2.0)
+                                * (k as f64 + 0.5);
+                            data[j] * weights[j] * angle.cos()
+                        })
+                        .sum::<f64>()
+                })
+                .collect::<Vec<_>>();
+            assert::close(&result, &expected, 1e-10 * n as f64);
+        }
+    }
+}
+
+#[test]
+fn mdct_reconstruction() {
+    for &n in &[2, 16, 256] {
+        for &window in &[
+            Window::Sine,
+            Window::KaiserBessel(4.0),
+            Window::KaiserBessel(6.0),
+        ] {
+            let frames = 8;
+            let data = (0..(frames * n))
+                .map(|i| (i as f64 * 0.37).sin() + ((i * 7) % 11) as f64)
+                .collect::<Vec<_>>();
+            let mut padded = vec![0.0; n];
+            padded.extend(&data);
+            padded.extend(vec![0.0; n]);
+            let mdct = Mdct::new(n, window);
+            let mut result = vec![0.0; padded.len()];
+            let mut coefficients = vec![0.0; n];
+            for i in 0..(frames + 1) {
+                let range = (i * n)..((i + 2) * n);
+                mdct.forward(&padded[range.clone()], &mut coefficients);
+                mdct.inverse(&coefficients, &mut result[range]);
+            }
+            assert::close(&result[n..((frames + 1) * n)], &data, 1e-12);
+        }
+    }
+}
+
+#[test]
+fn error_length_mismatch() {
+    let plan = Plan::new(Operation::Forward, 6);
+    let mut data = vec![c64::new(1.0, 0.0); 12];
+    assert_eq!(
+        try_transform(&mut data, &plan),
+        Err(Error::LengthMismatch { plan: 6, data: 12 })
+    );
+    let mut data = vec![1.0; 4];
+    assert_eq!(
+        try_transform(&mut data, &plan),
+        Err(Error::LengthMismatch { plan: 6, data: 4 })
+    );
+    let plan = Plan2::<f64>::new(Operation::Forward, 4, 6);
+    assert_eq!(
+        plan.try_r2c(&[0.0; 24], &mut [c64::new(0.0, 0.0); 5]),
+        Err(Error::LengthMismatch { plan: 16, data: 5 })
+    );
+    assert_eq!(
+        plan.try_r2c(&[0.0; 20], &mut [c64::new(0.0, 0.0); 16]),
+        Err(Error::LengthMismatch { plan: 24, data: 20 })
+    );
+    let mdct = Mdct::<f64>::new(4, Window::Sine);
+    assert_eq!(
+        mdct.try_forward(&[0.0; 8], &mut [0.0; 3]),
+        Err(Error::LengthMismatch { plan: 4, data: 3 })
+    );
+    assert_eq!(
+        mdct.try_inverse(&[0.0; 3], &mut [0.0; 8]),
+        Err(Error::LengthMismatch { plan: 4, data: 3 })
+    );
+    assert_eq!(
+        mdct.try_forward(&[0.0; 7], &mut [0.0; 4]),
+        Err(Error::LengthMismatch { plan: 8, data: 7 })
+    );
+}
+
+#[test]
+fn error_batch_length_mismatch() {
+    let plan = Plan::new(Operation::Forward, 8);
+    let mut data = vec![c64::new(1.0, 0.0); 30];
+    assert_eq!(
+        plan.try_transform_batch(&mut data, 4, 8),
+        Err(Error::LengthMismatch { plan: 8, data: 30 })
+    );
+    assert_eq!(
+        plan.try_transform_batch(&mut data, 4, 6),
+        Err(Error::LengthMismatch { plan: 8, data: 6 })
+    );
+    assert_eq!(
+        plan.try_transform_batch(&mut data, usize::MAX, 8),
+        Err(Error::LengthMismatch { plan: 8, data: 30 })
+    );
+}
+
+#[test]
+fn error_operation_mismatch() {
+    let data = vec![1.0; 8];
+    let mut result = vec![c64::new(0.0, 0.0); 5];
+    assert_eq!(
+        try_r2c(&data, &mut result, &Plan::new(Operation::Inverse, 8)),
+        Err(Error::OperationMismatch(Operation::Inverse))
+    );
+}
+
+#[test]
+fn error_scratch_too_short() {
+    let options = Options {
+        kernel: Kernel::Stockham,
+        ..Options::default()
+    };
+    let plan = Plan::with_options(Operation::Forward, 16, options);
+    let mut data = vec![c64::new(1.0, 0.0); 16];
+    let mut scratch = vec![c64::new(0.0, 0.0); 8];
+    assert_eq!(
+        try_transform_with_scratch(&mut data, &mut scratch, &plan),
+        Err(Error::ScratchTooShort {
+            data: 16,
+            scratch: 8
+        })
+    );
+}
+
+#[test]
+fn error_unsupported_axis() {
+    assert_eq!(
+        PlanN::<f64>::try_new(Operation::Forward, &[4, 4], &[0, 2]).unwrap_err(),
+        Error::UnsupportedAxis {
+            axis: 2,
+            dimensions: 2,
+        }
+    );
+}
+
+#[test]
+fn error_unsupported_modulus() {
+    assert_eq!(
+        ntt::Plan::try_new(Operation::Forward, 8, 1u32 << 20).unwrap_err(),
+        Error::UnsupportedModulus(1 << 20)
+    );
+    assert_eq!(
+        ntt::Plan::try_new(Operation::Forward, 8, 25u32).unwrap_err(),
+        Error::UnsupportedModulus(25)
+    );
+    assert_eq!(
+        ntt::Plan::try_new(Operation::Forward, 2, 91u64).unwrap_err(),
+        Error::UnsupportedModulus(91)
+    );
+    assert_eq!(
+        ntt::Plan::try_new(Operation::Forward, 2, 3_215_031_751u64).unwrap_err(),
+        Error::UnsupportedModulus(3_215_031_751)
+    );
+    assert_eq!(
+        ntt::Plan::try_new(Operation::Forward, 1 << 24, 998_244_353u32).unwrap_err(),
+        Error::UnsupportedLength(1 << 24)
+    );
+}
+
+#[test]
+fn error_unsupported_length() {
+    assert_eq!(
+        Plan::<f64>::try_new(Operation::Forward, 0).err(),
+        Some(Error::UnsupportedLength(0))
+    );
+    for &n in &[usize::MAX, usize::MAX / 4 + 1, usize::MAX / 4] {
+        assert_eq!(
+            Plan::<f64>::try_new(Operation::Forward, n).err(),
+            Some(Error::UnsupportedLength(n))
+        );
+    }
+}
+
+#[test]
+fn ntt_convolution() {
+    fn check<W>(modulus: W, values: u64)
+    where
+        W: ntt::Word + From<u32> + Into<u64>,
+    {
+        let m: u64 = modulus.into();
+        for &(n1, n2) in &[
+            (1, 1),
+            (1, 3),
+            (2, 2),
+            (5, 3),
+            (16, 16),
+            (100, 29),
+            (512, 512),
+        ] {
+            let a = (0..n1)
+                .map(|i| (i as u64 * 1_000_003 + 7) % values)
+                .collect::<Vec<_>>();
+            let b = (0..n2)
+                .map(|i| (i as u64 * 999_983 + 11) % values)
+                .collect::<Vec<_>>();
+            let mut expected = vec![0u128; n1 + n2 - 1];
+            for (i, &x) in a.iter().enumerate() {
+                for (j, &y) in b.iter().enumerate() {
+                    expected[i + j] = (expected[i + j] + x as u128 * y as u128) % m as u128;
+                }
+            }
+            let n = (n1 + n2 - 1).next_power_of_two();
+            let convert = |data: &[u64]| {
+                let mut result = vec![W::from(0); n];
+                for (value, &other) in result.iter_mut().zip(data) {
+                    *value = W::from(other as u32);
+                }
+                result
+            };
+            let (mut a, mut b) = (convert(&a), convert(&b));
+            let forward = ntt::Plan::new(Operation::Forward, n, modulus);
+            forward.transform(&mut a);
+            forward.transform(&mut b);
+            forward.multiply(&mut a, &b);
+            ntt::Plan::new(Operation::Inverse, n, modulus).transform(&mut a);
+            for (i, &value) in a.iter().enumerate() {
+                let expected = expected.get(i).cloned().unwrap_or(0);
+                assert_eq!(value.into() as u128, expected);
+            }
+        }
+    }
+
+    check(998_244_353u32, 998_244_353);
+    check(998_244_353u32, 1 << 32);
+    check(0xffff_ffff_0000_0001u64, 1 << 32);
+}
+
+#[test]
+fn ntt_inverse() {
+    let modulus = 0xffff_ffff_0000_0001u64;
+    for &n in &[1, 2, 4, 8, 64, 1024, 1 << 16] {
+        let data = (0..n)
+            .map(|i| (i as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15) % modulus)
+            .collect::<Vec<_>>();
+        let mut result = data.clone();
+        ntt::Plan::new(Operation::Forward, n, modulus).transform(&mut result);
+        let mut other = result.clone();
+        ntt::Plan::new(Operation::Inverse, n, modulus).transform(&mut result);
+        assert_eq!(result, data);
+        ntt::Plan::new(Operation::Backward, n, modulus).transform(&mut other);
+        let data = data
+            .iter()
+            .map(|&value| (value as u128 * n as u128 % modulus as u128) as u64)
+            .collect::<Vec<_>>();
+        assert_eq!(other, data);
+    }
+}
+
+#[test]
+fn ntt_combine() {
+    let moduli = [998_244_353u32, 167_772_161, 469_762_049];
+    let (n1, n2) = (300, 200);
+    let a = (0..n1)
+        .map(|i| (i as u64 * 2_654_435_761) % 1_000_000_000)
+        .collect::<Vec<_>>();
+    let b = (0..n2)
+        .map(|i| (i as u64 * 2_246_822_519 + 12_345) % 1_000_000_000)
+        .collect::<Vec<_>>();
+    let mut expected = vec![0u128; n1 + n2 - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            expected[i + j] += x as u128 * y as u128;
+        }
+    }
+    let n = (n1 + n2 - 1).next_power_of_two();
+    let residues = moduli
+        .iter()
+        .map(|&modulus| {
+            let convert = |data: &[u64]| {
+                let mut result = vec![0u32; n];
+                for (value, &other) in result.iter_mut().zip(data) {
+                    *value = (other % modulus as u64) as u32;
+                }
+                result
+            };
+            let (mut a, mut b) = (convert(&a), convert(&b));
+            let forward = ntt::Plan::new(Operation::Forward, n, modulus);
+            forward.transform(&mut a);
+            forward.transform(&mut b);
+            forward.multiply(&mut a, &b);
+            ntt::Plan::new(Operation::Inverse, n, modulus).transform(&mut a);
+            a
+        })
+        .collect::<Vec<_>>();
+    let moduli = moduli
+        .iter()
+        .map(|&modulus| modulus as u64)
+        .collect::<Vec<_>>();
+    for (i, &expected) in expected.iter().enumerate() {
+        let residues = residues
+            .iter()
+            .map(|residues| residues[i] as u64)
+            .collect::<Vec<_>>();
+        assert_eq!(ntt::combine(&residues, &moduli), expected);
+    }
+    assert!(expected.iter().any(|&value| value > u64::MAX as u128));
+}
+
 #[test]
 fn real_forward_1() {
     let mut data = vec![1.0];
@@ -98,6 +946,365 @@ fn real_forward_512() {
     );
 }
 
+#[test]
+fn real_forward_even() {
+    for &n in &[6, 10, 22, 26, 1000, 2 * 4099] {
+        let data = signal(n).iter().map(|value| value.re).collect::<Vec<_>>();
+        let mut expected = to_c64(&data);
+        transform(&mut expected, &Plan::new(Operation::Forward, n));
+        let mut result = data.clone();
+        transform(&mut result, &Plan::new(Operation::Forward, n));
+        assert::close(as_f64(&unpack(&result)), as_f64(&expected), 1e-10);
+        let options = Options {
+            real: true,
+            ..Options::default()
+        };
+        let mut result = data;
+        transform(
+            &mut result,
+            &Plan::with_options(Operation::Forward, n, options),
+        );
+        assert::close(as_f64(&unpack(&result)), as_f64(&expected), 1e-10);
+    }
+}
+
+#[test]
+fn real_forward_odd() {
+    for &n in &[3, 5, 15, 23, 97, 1001] {
+        let data = signal(n).iter().map(|value| value.re).collect::<Vec<_>>();
+        let mut expected = to_c64(&data);
+        transform(&mut expected, &Plan::new(Operation::Forward, n));
+        let mut data = data;
+        transform(&mut data, &Plan::new(Operation::Forward, n));
+        assert_eq!(data[0], expected[0].re);
+        assert::close(&data[1..], as_f64(&expected[1..(n / 2 + 1)]), 1e-10);
+        assert::close(as_f64(&unpack(&data)), as_f64(&expected), 1e-10);
+    }
+}
+
+#[test]
+fn real_forward_out_of_place() {
+    for &n in &[1, 2, 3, 8, 15, 22, 256, 1000] {
+        let data = signal(n).iter().map(|value| value.re).collect::<Vec<_>>();
+        let mut expected = data.clone();
+        transform(&mut expected, &Plan::new(Operation::Forward, n));
+        let expected = unpack(&expected);
+        let mut result = vec![c64::new(0.0, 0.0); n / 2 + 1];
+        r2c(&data, &mut result, &Plan::new(Operation::Forward, n));
+        assert::close(as_f64(&result), as_f64(&expected[..(n / 2 + 1)]), 1e-12);
+    }
+}
+
+#[test]
+fn real_inverse_out_of_place() {
+    for &n in &[1, 2, 3, 8, 15, 22, 256, 1000] {
+        let data = signal(n).iter().map(|value| value.re).collect::<Vec<_>>();
+        let mut spectrum = vec![c64::new(0.0, 0.0); n / 2 + 1];
+        r2c(&data, &mut spectrum, &Plan::new(Operation::Forward, n));
+        let mut result = vec![0.0; n];
+        c2r(&spectrum, &mut result, &Plan::new(Operation::Inverse, n));
+        assert::close(&result, &data, 1e-12);
+    }
+}
+
+#[test]
+fn real_inverse_packed() {
+    for &n in &[15, 16] {
+        let data = signal(n).iter().map(|value| value.re).collect::<Vec<_>>();
+        let mut spectrum = to_c64(&data);
+        transform(&mut spectrum, &Plan::new(Operation::Forward, n));
+        for value in &mut spectrum[3..(n - 2)] {
+            *value = c64::new(0.0, 0.0);
+        }
+        let mut expected = spectrum.clone();
+        transform(&mut expected, &Plan::new(Operation::Inverse, n));
+        let mut result = pack(&spectrum);
+        transform(&mut result, &Plan::new(Operation::Inverse, n));
+        let expected = expected.iter().map(|value| value.re).collect::<Vec<_>>();
+        assert::close(&result, &expected, 1e-14);
+    }
+}
+
+#[test]
+fn real_normalization() {
+    for &n in &[16, 15, 22] {
+        let data = signal(n).iter().map(|value| value.re).collect::<Vec<_>>();
+        let plan = |operation, normalization| {
+            let options = Options {
+                normalization,
+                ..Options::default()
+            };
+            Plan::with_options(operation, n, options)
+        };
+
+        let mut result = data.clone();
+        transform(
+            &mut result,
+            &plan(Operation::Forward, Normalization::Orthonormal),
+        );
+        let energy = unpack(&result)
+            .iter()
+            .map(|value| value.norm_sqr())
+            .sum::<f64>();
+        assert::close(
+            energy,
+            data.iter().map(|value| value * value).sum::<f64>(),
+            1e-10,
+        );
+        transform(
+            &mut result,
+            &plan(Operation::Inverse, Normalization::Orthonormal),
+        );
+        assert::close(&result, &data, 1e-12);
+
+        let mut result = data.clone();
+        transform(
+            &mut result,
+            &plan(Operation::Forward, Normalization::Forward),
+        );
+        transform(
+            &mut result,
+            &plan(Operation::Backward, Normalization::Forward),
+        );
+        assert::close(&result, &data, 1e-12);
+
+        let mut result = data.clone();
+        transform(&mut result, &Plan::new(Operation::Forward, n));
+        transform(&mut result, &Plan::new(Operation::Backward, n));
+        let scaled = data
+            .iter()
+            .map(|value| value * n as f64)
+            .collect::<Vec<_>>();
+        assert::close(&result, &scaled, 1e-11);
+
+        let mut result = data.clone();
+        transform(
+            &mut result,
+            &plan(Operation::Forward, Normalization::Orthonormal),
+        );
+        let mut expected = unpack(&result);
+        transform(
+            &mut result,
+            &plan(Operation::Backward, Normalization::Orthonormal),
+        );
+        transform(
+            &mut expected,
+            &plan(Operation::Backward, Normalization::Orthonormal),
+        );
+        let expected = expected.iter().map(|value| value.re).collect::<Vec<_>>();
+        assert::close(&result, &expected, 1e-11);
+
+        let mut spectrum = vec![c64::new(0.0, 0.0); n / 2 + 1];
+        r2c(
+            &data,
+            &mut spectrum,
+            &plan(Operation::Forward, Normalization::Orthonormal),
+        );
+        let mut result = vec![0.0; n];
+        c2r(
+            &spectrum,
+            &mut result,
+            &plan(Operation::Inverse, Normalization::Orthonormal),
+        );
+        assert::close(&result, &data, 1e-12);
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn real_parallel() {
+    let n = 1 << 16;
+    let data = signal(n).iter().map(|value| value.re).collect::<Vec<_>>();
+    let options = Options {
+        threads: 4,
+        ..Options::default()
+    };
+    let mut expected = data.clone();
+    transform(&mut expected, &Plan::new(Operation::Forward, n));
+    let mut result = data.clone();
+    transform(
+        &mut result,
+        &Plan::with_options(Operation::Forward, n, options),
+    );
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn real_four_step() {
+    let options = Options {
+        four_step: Some(1000),
+        ..Options::default()
+    };
+    for &n in &[1 << 12, 1 << 13, 44100] {
+        let data = signal(n).iter().map(|value| value.re).collect::<Vec<_>>();
+        let mut expected = data.clone();
+        transform(&mut expected, &Plan::new(Operation::Forward, n));
+        let mut result = data.clone();
+        transform(
+            &mut result,
+            &Plan::with_options(Operation::Forward, n, options),
+        );
+        assert::close(&result, &expected, 1e-8);
+        transform(
+            &mut result,
+            &Plan::with_options(Operation::Inverse, n, options),
+        );
+        assert::close(&result, &data, 1e-10);
+    }
+}
+
+#[test]
+fn real_batch() {
+    let cases = [
+        (8, 5, 8),
+        (15, 4, 20),
+        (1024, 3, 1030),
+        (23, 3, 24),
+        (46, 3, 50),
+        (29, 2, 30),
+    ];
+    for &(n, count, distance) in &cases {
+        let data = (0..(count * distance))
+            .map(|i| (i as f64).sin())
+            .collect::<Vec<_>>();
+        for &operation in &[Operation::Forward, Operation::Inverse] {
+            let plan = Plan::new(operation, n);
+            let mut expected = data.clone();
+            for chunk in expected.chunks_mut(distance) {
+                transform(&mut chunk[..n], &plan);
+            }
+            let mut result = data.clone();
+            plan.transform_batch(&mut result, count, distance);
+            assert_eq!(result, expected);
+        }
+    }
+}
+
+#[test]
+fn real_strided() {
+    let (n, channels) = (16, 2);
+    let data = (0..(n * channels))
+        .map(|i| (i as f64).sin())
+        .collect::<Vec<_>>();
+    for channel in 0..channels {
+        let plan = Plan::new(Operation::Forward, n);
+        let mut expected = data.clone();
+        let mut gathered = (0..n)
+            .map(|i| data[channel + i * channels])
+            .collect::<Vec<_>>();
+        transform(&mut gathered, &plan);
+        for (i, value) in gathered.into_iter().enumerate() {
+            expected[channel + i * channels] = value;
+        }
+        let mut result = data.clone();
+        transform(&mut Strided::new(&mut result, channel, channels, n), &plan);
+        assert_eq!(result, expected);
+    }
+    let mut data = data;
+    assert_eq!(
+        try_transform(
+            &mut Strided::new(&mut data, 0, channels, n - 1),
+            &Plan::new(Operation::Forward, n)
+        ),
+        Err(Error::LengthMismatch {
+            plan: n,
+            data: n - 1
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "the view (5) does not fit in the data (4)")]
+fn strided_offset_out_of_bounds() {
+    let mut data = vec![c64::new(0.0, 0.0); 4];
+    Strided::new(&mut data, 5, 1, 0);
+}
+
+#[test]
+fn error_strided() {
+    let mut data = vec![c64::new(0.0, 0.0); 10];
+    assert!(Strided::try_new(&mut data, 0, 1, 10).is_ok());
+    assert!(Strided::try_new(&mut data, 1, 3, 3).is_ok());
+    assert!(Strided::try_new(&mut data, 10, 1, 0).is_ok());
+    assert_eq!(
+        Strided::try_new(&mut data, 0, 0, 4).err(),
+        Some(Error::UnsupportedStride(0))
+    );
+    assert_eq!(
+        Strided::try_new(&mut data, 2, 3, 4).err(),
+        Some(Error::ViewOutOfBounds { view: 12, data: 10 })
+    );
+    assert_eq!(
+        Strided::try_new(&mut data, 1, usize::MAX, 3).err(),
+        Some(Error::ViewOutOfBounds {
+            view: usize::MAX,
+            data: 10
+        })
+    );
+}
+
+#[test]
+fn real_forward_2d() {
+    for &(rows, columns) in &[(1, 1), (1, 2), (4, 6), (5, 7), (8, 8), (3, 17), (20, 32)] {
+        let data = (0..(rows * columns))
+            .map(|i| ((i * 7) % 11) as f64 - 5.0)
+            .collect::<Vec<_>>();
+        let mut result = vec![c64::new(0.0, 0.0); rows * (columns / 2 + 1)];
+        Plan2::new(Operation::Forward, rows, columns).r2c(&data, &mut result);
+        assert::close(
+            as_f64(&unpack2(&result, rows, columns)),
+            as_f64(&naive2(&to_c64(&data), rows, columns, Operation::Forward)),
+            1e-10,
+        );
+        let mut other = vec![0.0; rows * columns];
+        Plan2::new(Operation::Inverse, rows, columns).c2r(&result, &mut other);
+        assert::close(&other, &data, 1e-12);
+    }
+}
+
+#[test]
+fn real_hartley() {
+    use std::f64::consts::PI;
+
+    for n in (1..33).chain(vec![64, 100, 127, 256]) {
+        let data = (0..n)
+            .map(|i| ((i * 7) % 11) as f64 - 5.0)
+            .collect::<Vec<_>>();
+        let expected = (0..n)
+            .map(|k| {
+                data.iter()
+                    .enumerate()
+                    .map(|(j, &value)| {
+                        let angle = 2.0 * PI * ((j * k) % n) as f64 / n as f64;
+                        value * (angle.cos() + angle.sin())
+                    })
+                    .sum::<f64>()
+            })
+            .collect::<Vec<_>>();
+        let mut result = data.clone();
+        hartley(&mut result, &Plan::new(Operation::Forward, n));
+        assert::close(&result, &expected, 1e-10 * n as f64);
+
+        let spectrum = naive(&to_c64(&data), Operation::Forward);
+        assert::close(
+            as_f64(&unpack_hartley(&result)),
+            as_f64(&spectrum),
+            1e-10 * n as f64,
+        );
+        assert::close(pack_hartley(&spectrum), &expected, 1e-10 * n as f64);
+
+        let mut other = result.clone();
+        hartley(&mut other, &Plan::new(Operation::Backward, n));
+        let other = other
+            .iter()
+            .map(|&value| value / n as f64)
+            .collect::<Vec<_>>();
+        assert::close(&other, &data, 1e-12);
+        hartley(&mut result, &Plan::new(Operation::Inverse, n));
+        assert::close(&result, &data, 1e-12);
+    }
+}
+
 #[test]
 fn real_inverse_256() {
     let mut data = fixtures::FREQUENCY_DATA_256_REAL_PACKED.to_vec();
@@ -113,6 +1320,210 @@ fn real_inverse_512() {
     assert::close(&data, &fixtures::TIME_DATA_512[..], 1e-14);
 }
 
+#[test]
+fn real_inverse_any() {
+    for &n in &[3, 6, 15, 22, 23, 1000, 1001, 2 * 4099] {
+        let data = signal(n).iter().map(|value| value.re).collect::<Vec<_>>();
+        let mut result = data.clone();
+        transform(&mut result, &Plan::new(Operation::Forward, n));
+        transform(&mut result, &Plan::new(Operation::Inverse, n));
+        assert::close(&result, &data, 1e-12);
+    }
+}
+
+fn naive(data: &[c64], operation: Operation) -> Vec<c64> {
+    let n = data.len();
+    let sign = if let Operation::Forward = operation {
+        -1.0
+    } else {
+        1.0
+    };
+    (0..n)
+        .map(|k| {
+            data.iter()
+                .enumerate()
+                .fold(c64::new(0.0, 0.0), |sum, (j, &value)| {
+                    let theta = sign * 2.0 * std::f64::consts::PI * ((j * k) % n) as f64 / n as f64;
+                    sum + value * c64::new(theta.cos(), theta.sin())
+                })
+        })
+        .collect()
+}
+
+fn naive2(data: &[c64], rows: usize, columns: usize, operation: Operation) -> Vec<c64> {
+    let sign = if let Operation::Forward = operation {
+        -1.0
+    } else {
+        1.0
+    };
+    let mut result = vec![c64::new(0.0, 0.0); rows * columns];
+    for (k, value) in result.iter_mut().enumerate() {
+        let (k, l) = (k / columns, k % columns);
+        for (j, &datum) in data.iter().enumerate() {
+            let (i, j) = (j / columns, j % columns);
+            let theta = sign
+                * 2.0
+                * std::f64::consts::PI
+                * (((i * k) % rows) as f64 / rows as f64
+                    + ((j * l) % columns) as f64 / columns as f64);
+            *value += datum * c64::new(theta.cos(), theta.sin());
+        }
+    }
+    result
+}
+
+fn naive_axis(data: &[c64], shape: &[usize], axis: usize, operation: Operation) -> Vec<c64> {
+    let n = shape[axis];
+    let stride: usize = shape[(axis + 1)..].iter().product();
+    let mut result = data.to_vec();
+    for i in 0..(data.len() / n) {
+        let offset = (i / stride) * n * stride + i % stride;
+        let line = (0..n)
+            .map(|j| data[offset + j * stride])
+            .collect::<Vec<_>>();
+        for (j, value) in naive(&line, operation).into_iter().enumerate() {
+            result[offset + j * stride] = value;
+        }
+    }
+    result
+}
+
+fn naive_dct(data: &[f64], kind: dct::Type, normalization: Normalization) -> Vec<f64> {
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+    let n = data.len();
+    let m = if kind == dct::Type::I { n - 1 } else { n };
+    let edge = |i: usize| i == 0 || i == n - 1;
+    let weight = |j: usize, k: usize| match normalization {
+        Normalization::Orthonormal => {
+            let weight = match kind {
+                dct::Type::I if edge(j) && edge(k) => 0.5,
+                dct::Type::I if edge(j) || edge(k) => FRAC_1_SQRT_2,
+                dct::Type::II if k == 0 => FRAC_1_SQRT_2,
+                dct::Type::III if j == 0 => FRAC_1_SQRT_2,
+                _ => 1.0,
+            };
+            weight * (2.0 / m as f64).sqrt()
+        }
+        _ => match kind {
+            dct::Type::I if edge(j) => 0.5,
+            dct::Type::III if j == 0 => 0.5,
+            _ => 1.0,
+        },
+    };
+    (0..n)
+        .map(|k| {
+            data.iter()
+                .enumerate()
+                .map(|(j, &value)| {
+                    let (j1, k1) = match kind {
+                        dct::Type::I => (j as f64, k as f64),
+                        dct::Type::II => (j as f64 + 0.5, k as f64),
+                        dct::Type::III => (j as f64, k as f64 + 0.5),
+                        dct::Type::IV => (j as f64 + 0.5, k as f64 + 0.5),
+                    };
+                    value * weight(j, k) * (PI * j1 * k1 / m as f64).cos()
+                })
+                .sum()
+        })
+        .collect()
+}
+
+fn naive_dst(data: &[f64], kind: dst::Type, normalization: Normalization) -> Vec<f64> {
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+    let n = data.len();
+    let m = if kind == dst::Type::I { n + 1 } else { n };
+    let weight = |j: usize, k: usize| match normalization {
+        Normalization::Orthonormal => {
+            let weight = match kind {
+                dst::Type::II if k == n - 1 => FRAC_1_SQRT_2,
+                dst::Type::III if j == n - 1 => FRAC_1_SQRT_2,
+                _ => 1.0,
+            };
+            weight * (2.0 / m as f64).sqrt()
+        }
+        _ => match kind {
+            dst::Type::III if j == n - 1 => 0.5,
+            _ => 1.0,
+        },
+    };
+    (0..n)
+        .map(|k| {
+            data.iter()
+                .enumerate()
+                .map(|(j, &value)| {
+                    let (j1, k1) = match kind {
+                        dst::Type::I => (j as f64 + 1.0, k as f64 + 1.0),
+                        dst::Type::II => (j as f64 + 0.5, k as f64 + 1.0),
+                        dst::Type::III => (j as f64 + 1.0, k as f64 + 0.5),
+                        dst::Type::IV => (j as f64 + 0.5, k as f64 + 0.5),
+                    };
+                    value * weight(j, k) * (PI * j1 * k1 / m as f64).sin()
+                })
+                .sum()
+        })
+        .collect()
+}
+
+fn window_value(window: Window, n: usize, j: usize) -> f64 {
+    use std::f64::consts::PI;
+
+    match window {
+        Window::Sine => (PI * (j as f64 + 0.5) / (2 * n) as f64).sin(),
+        Window::KaiserBessel(alpha) => {
+            let bessel = |x: f64| {
+                (0..50)
+                    .scan(1.0, |term, k| {
+                        if k > 0 {
+                            *term *= x * x / 4.0 / (k * k) as f64;
+                        }
+                        Some(*term)
+                    })
+                    .sum::<f64>()
+            };
+            let kaiser = |i: usize| {
+                let x = 2.0 * i as f64 / n as f64 - 1.0;
+                bessel(PI * alpha * (1.0 - x * x).sqrt())
+            };
+            let j = if j < n { j } else { 2 * n - 1 - j };
+            let total = (0..(n + 1)).map(kaiser).sum::<f64>();
+            ((0..(j + 1)).map(kaiser).sum::<f64>() / total).sqrt()
+        }
+    }
+}
+
+const NORMALIZATIONS: [Normalization; 4] = [
+    Normalization::Inverse,
+    Normalization::Orthonormal,
+    Normalization::Forward,
+    Normalization::None,
+];
+
+fn signal(n: usize) -> Vec<c64> {
+    (0..n)
+        .map(|i| c64::new((i as f64).sin(), (2.0 * i as f64).cos()))
+        .collect()
+}
+
+#[cfg(target_arch = "x86_64")]
+fn supported(simd: Simd) -> bool {
+    match simd {
+        Simd::Sse2 => is_x86_feature_detected!("sse2"),
+        Simd::Avx => is_x86_feature_detected!("avx"),
+        Simd::Avx2 => is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma"),
+        _ => true,
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn supported(simd: Simd) -> bool {
+    match simd {
+        Simd::Sse2 | Simd::Avx | Simd::Avx2 => false,
+        _ => true,
+    }
+}
+
 fn as_f64(slice: &[c64]) -> &[f64] {
     unsafe { std::slice::from_raw_parts(slice.as_ptr() as *const _, 2 * slice.len()) }
 }